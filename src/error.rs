@@ -0,0 +1,40 @@
+use std::fmt;
+
+/// An error that prevents a search from starting or finishing
+#[derive(Debug)]
+pub enum Error {
+    /// The query is not a valid regular expression
+    InvalidQuery(String),
+
+    /// A file type string is not one of the supported [FileType](crate::FileType)s
+    UnknownFileType(String),
+
+    /// None of these file paths contain a file of a supported [FileType](crate::FileType)
+    CannotGuessFileType(Vec<String>),
+
+    /// Stdin is being searched without a way to know its [FileType](crate::FileType)
+    ///
+    /// Stdin needs either [Args::stdin_filename](crate::Args::stdin_filename) or exactly one
+    /// [Args::file_type](crate::Args::file_type).
+    StdinFileTypeRequired,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidQuery(query) => write!(f, "Invalid query '{query}'"),
+            Error::UnknownFileType(file_type) => write!(f, "Invalid file type '{file_type}'"),
+            Error::CannotGuessFileType(file_paths) => write!(
+                f,
+                "Unable to guess file type from file paths '{}'",
+                file_paths.join(" ")
+            ),
+            Error::StdinFileTypeRequired => write!(
+                f,
+                "Searching stdin requires --stdin-filename or a single --type"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -2,22 +2,28 @@ use super::FileType;
 use ignore::Walk;
 use memchr::memmem;
 use regex::Regex;
-use std::fs;
-use std::io::Read;
+use strum::IntoEnumIterator;
 
 pub fn get_regexp_for_file_type(file_type: &FileType) -> Regex {
     let regexp_string = match file_type {
         FileType::JS => &r"\.(js|jsx|ts|tsx|mjs|cjs)$".to_string(),
         FileType::PHP => &r"\.php$".to_string(),
         FileType::RS => &r"\.rs$".to_string(),
+        FileType::PY => &r"\.(py|pyi)$".to_string(),
+        FileType::GO => &r"\.go$".to_string(),
+        FileType::JAVA => &r"\.java$".to_string(),
+        FileType::KT => &r"\.(kt|kts)$".to_string(),
+        FileType::C => &r"\.(c|h|cc|cpp|cxx|hpp|hh)$".to_string(),
+        FileType::RB => &r"\.(rb|rake)$".to_string(),
+        FileType::CS => &r"\.cs$".to_string(),
+        FileType::SWIFT => &r"\.swift$".to_string(),
+        FileType::DART => &r"\.dart$".to_string(),
+        FileType::SCALA => &r"\.(scala|sc)$".to_string(),
     };
     Regex::new(regexp_string).expect("Could not create regex for file extension")
 }
 
 pub fn guess_file_type_from_file_path(file_path: &str) -> Option<FileType> {
-    let js_regex = get_regexp_for_file_type(&FileType::JS);
-    let php_regex = get_regexp_for_file_type(&FileType::PHP);
-    let rs_regex = get_regexp_for_file_type(&FileType::RS);
     for entry in Walk::new(file_path) {
         let path = match entry {
             Ok(path) => path.into_path(),
@@ -26,48 +32,125 @@ pub fn guess_file_type_from_file_path(file_path: &str) -> Option<FileType> {
         if path.is_dir() {
             continue;
         }
-        let path = match path.to_str() {
-            Some(p) => p.to_string(),
-            None => String::from(""),
-        };
-        if js_regex.is_match(&path) {
-            return Some(FileType::JS);
-        }
-        if php_regex.is_match(&path) {
-            return Some(FileType::PHP);
-        }
-        if rs_regex.is_match(&path) {
-            return Some(FileType::RS);
+        if let Some(file_type) = guess_file_type_from_file_name(&path.to_string_lossy()) {
+            return Some(file_type);
         }
     }
     None
 }
 
-pub fn does_file_match_regexp(mut file: &fs::File, re: &Regex) -> bool {
-    let mut buf = String::new();
-    let bytes = file.read_to_string(&mut buf);
-    if bytes.unwrap_or(0) == 0 {
-        return false;
-    }
-    re.is_match(&buf)
+pub fn guess_file_type_from_file_name(file_name: &str) -> Option<FileType> {
+    FileType::iter().find(|file_type| get_regexp_for_file_type(file_type).is_match(file_name))
 }
 
-pub fn does_file_match_query(mut file: &fs::File, query: &str) -> bool {
-    let mut full: Vec<u8> = vec![];
-    let mut buf = [0u8; 2048];
-    let finder = memmem::Finder::new(query);
-    loop {
-        let bytes = file.read(&mut buf);
-        if bytes.unwrap_or(0) == 0 {
-            break false;
-        }
-        if full.contains(&0xA) {
-            let mut split_full = full.rsplit(|&b| b == b'\n');
-            full = split_full.next().unwrap_or(&[0u8, 1]).to_vec();
+/// The searchable text of a file and how to find positions in the file from positions in it
+pub struct DecodedText {
+    pub text: String,
+    encoding: Encoding,
+}
+
+enum Encoding {
+    /// UTF-8 after a byte order mark of `bom_len` bytes (or none)
+    ///
+    /// Each replacement holds the end of a U+FFFD in the text and the end of the invalid bytes it
+    /// replaced in the file.
+    Utf8 {
+        bom_len: usize,
+        replacements: Vec<(usize, usize)>,
+    },
+
+    /// UTF-16 after a two byte byte order mark
+    Utf16,
+}
+
+impl DecodedText {
+    /// Return the position in the file's bytes of a position in the text
+    pub fn get_file_offset(&self, text_offset: usize) -> usize {
+        match &self.encoding {
+            Encoding::Utf8 {
+                bom_len,
+                replacements,
+            } => {
+                let index = replacements.partition_point(|(text_end, _)| *text_end <= text_offset);
+                let (text_end, file_end) = match index {
+                    0 => (0, *bom_len),
+                    _ => replacements[index - 1],
+                };
+                file_end + text_offset - text_end
+            }
+            Encoding::Utf16 => 2 + self.text[..text_offset].encode_utf16().count() * 2,
         }
-        full.extend(buf);
-        if finder.find(&full).is_some() {
-            break true;
+    }
+}
+
+/// Turn the contents of a file into text that can be searched
+///
+/// Files with a UTF-16 byte order mark are transcoded and a UTF-8 byte order mark is removed.
+/// Anything that is not valid UTF-8 is replaced with U+FFFD so that one stray byte (eg: in a
+/// Latin-1 file) doesn't prevent the rest of the file from being searched.
+pub fn decode_text(bytes: Vec<u8>) -> DecodedText {
+    match bytes.as_slice() {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => decode_utf8_lossy(rest, 3),
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        _ => match String::from_utf8(bytes) {
+            Ok(text) => DecodedText {
+                text,
+                encoding: Encoding::Utf8 {
+                    bom_len: 0,
+                    replacements: vec![],
+                },
+            },
+            Err(err) => decode_utf8_lossy(err.as_bytes(), 0),
+        },
+    }
+}
+
+fn decode_utf8_lossy(bytes: &[u8], bom_len: usize) -> DecodedText {
+    let mut text = String::with_capacity(bytes.len());
+    let mut replacements = vec![];
+    let mut file_end = bom_len;
+    for chunk in bytes.utf8_chunks() {
+        text.push_str(chunk.valid());
+        file_end += chunk.valid().len();
+        if !chunk.invalid().is_empty() {
+            text.push(char::REPLACEMENT_CHARACTER);
+            file_end += chunk.invalid().len();
+            replacements.push((text.len(), file_end));
         }
     }
+    DecodedText {
+        text,
+        encoding: Encoding::Utf8 {
+            bom_len,
+            replacements,
+        },
+    }
+}
+
+fn decode_utf16(bytes: &[u8], to_code_unit: fn([u8; 2]) -> u16) -> DecodedText {
+    let code_units = bytes
+        .chunks_exact(2)
+        .map(|pair| to_code_unit([pair[0], pair[1]]));
+    DecodedText {
+        text: char::decode_utf16(code_units)
+            .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect(),
+        encoding: Encoding::Utf16,
+    }
+}
+
+pub fn does_text_match_regexp(text: &str, re: &Regex) -> bool {
+    re.is_match(text)
+}
+
+pub fn does_text_match_query(text: &str, query: &str, ignore_case: bool) -> bool {
+    if ignore_case {
+        return memmem::find(
+            text.to_lowercase().as_bytes(),
+            query.to_lowercase().as_bytes(),
+        )
+        .is_some();
+    }
+    memmem::find(text.as_bytes(), query.as_bytes()).is_some()
 }
@@ -1,7 +1,8 @@
 #![warn(missing_docs)]
 //! Quick search for symbol definitions in various programming languages
 //!
-//! Currently this supports Rust, JS (or TypeScript), and PHP.
+//! Currently this supports Rust, JS (or TypeScript), PHP, Python, Go, Java, Kotlin, C (or C++),
+//! Ruby, C#, Swift, Dart, and Scala.
 //!
 //! This can be used like "Go to definition" in an IDE, except that instead of using a language
 //! server, it just searches for the definition using text parsing. This is less accurate but often
@@ -30,6 +31,13 @@
 //! // ./src/queries.js:17:function parseQuery {
 //! ```
 //!
+//! Like `ripgrep`, the `--column` option also includes the column number of each symbol.
+//!
+//! ```text
+//! $ grepdef --column parseQuery ./src
+//! // ./src/queries.js:17:10:function parseQuery {
+//! ```
+//!
 //! The search will be faster if you specify what type of file you are searching for using the
 //! `--type` option.
 //!
@@ -38,7 +46,72 @@
 //! // ./src/queries.js:17:function parseQuery {
 //! ```
 //!
-//! To use the crate from other Rust code, use [Searcher].
+//! To ignore the case of the symbol, use `-i` (or `--ignore-case`). Like `ripgrep`, `-S` (or
+//! `--smart-case`) ignores case only if the symbol is all lowercase.
+//!
+//! ```text
+//! $ grepdef -i -n parsequery ./src
+//! // ./src/queries.js:17:function parseQuery {
+//! ```
+//!
+//! To search more than one type of file, repeat the `--type` option, separate the types with
+//! commas, or use `--type all` to search every supported language at once.
+//!
+//! ```text
+//! $ grepdef --type js,php -n parseQuery ./src
+//! // ./src/queries.js:17:function parseQuery {
+//! // ./src/Queries.php:8:function parseQuery() {
+//! ```
+//!
+//! For editor plugins and other programs, the `--format` option can print each result as JSON
+//! (`json` for a single array or `ndjson` for one object per line).
+//!
+//! ```text
+//! $ grepdef --format ndjson parseQuery ./src
+//! // {"file_path":"./src/queries.js","line_number":17,"column":10,"byte_offset":321,"symbol":"parseQuery","kind":"function","text":"function parseQuery {"}
+//! ```
+//!
+//! To only find certain kinds of definitions, use the `--kind` option. For example, this will
+//! find a class named `Query` but not a function named `Query`.
+//!
+//! ```text
+//! $ grepdef --kind class,interface Query ./src
+//! // ./src/queries.js:class Query {
+//! ```
+//!
+//! To jump to definitions from an editor, use `--vimgrep` (for example, with Vim's
+//! `:cexpr system('grepdef --vimgrep parseQuery ./src')`) or `--format emacs` (for example, with
+//! Emacs' `M-x compile`). These always include line and column numbers and are never colored.
+//!
+//! ```text
+//! $ grepdef --vimgrep parseQuery ./src
+//! // ./src/queries.js:17:10:function parseQuery {
+//! ```
+//!
+//! To search text that isn't saved in a file, like an editor buffer or the output of `git show`,
+//! use `-` as the file path to read from stdin. Since there's no file name, either set `--type`
+//! or use `--stdin-filename` to name the file.
+//!
+//! ```text
+//! $ git show HEAD:src/queries.js | grepdef -n --stdin-filename src/queries.js parseQuery -
+//! // src/queries.js:17:function parseQuery {
+//! ```
+//!
+//! To stop after the first few results, use `--max-count` (or `-m`). For example,
+//! `grepdef -m 1 parseQuery` stops searching as soon as it finds one definition.
+//!
+//! Results are sorted by file path and line number. Use `--sort none` to get results in whatever
+//! order they are found, or `--sort relevance` to list exact matches closest to the top of the
+//! directory tree first.
+//!
+//! Files that cannot be searched (for example, because of their permissions) are skipped with a
+//! warning printed to stderr; use `--no-messages` to hide these warnings.
+//!
+//! Like `grep`, the CLI exits with status 0 if any definition is found, 1 if none are found, and
+//! 2 if there is an error, including when any file had to be skipped.
+//!
+//! To use the crate from other Rust code, use [Searcher]. Results can be collected with
+//! [Searcher::search] or handled one at a time as they are found with [Searcher::search_with].
 //!
 //! ```
 //! use grepdef::{Args, Searcher};
@@ -50,21 +123,31 @@
 
 use clap::Parser;
 use colored::Colorize;
-use ignore::Walk;
+use file_type::DecodedText;
+use ignore::{Walk, WalkBuilder};
+use query::{LineState, QueryRegexes};
 use regex::Regex;
-use std::error::Error;
+use serde::{Serialize, Serializer};
+use std::collections::BTreeMap;
 use std::fs;
-use std::io::{self, BufRead, Seek};
+use std::io::{self, Read};
 use std::num::NonZero;
-use std::sync::Arc;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc;
 use std::sync::Mutex;
 use std::time;
+use strum::IntoEnumIterator;
 use strum_macros::Display;
+use strum_macros::EnumIter;
 use strum_macros::EnumString;
 
+mod error;
 mod file_type;
+mod query;
 mod threads;
 
+pub use error::Error;
+
 /// The command-line arguments to be used by [Searcher]
 ///
 /// Can be passed to [Searcher::new].
@@ -86,21 +169,74 @@ pub struct Args {
     /// (Required) The symbol name (function, class, etc.) to search for
     pub query: String,
 
-    /// The file path(s) to search; recursively searches directories and respects .gitignore
+    /// The file path(s) to search; recursively searches directories and respects .gitignore; use
+    /// `-` to search stdin
     pub file_path: Option<Vec<String>>,
 
-    /// The file type to search (js, php, rs); will guess if not set but this is slower
-    #[arg(short = 't', long = "type")]
-    pub file_type: Option<String>,
+    /// The file type(s) to search (js, php, rs, py, go, java, kt, c, rb, cs, swift, dart, scala,
+    /// or all); can be repeated or comma-separated; will guess if not set but this is slower
+    #[arg(short = 't', long = "type", value_delimiter = ',')]
+    pub file_type: Option<Vec<String>>,
+
+    /// Match the query without regard to case if set
+    #[arg(short = 'i', long = "ignore-case")]
+    pub ignore_case: bool,
+
+    /// Match the query without regard to case if set, unless the query has an uppercase letter
+    #[arg(short = 'S', long = "smart-case")]
+    pub smart_case: bool,
 
     /// Show line numbers of matches if set
     #[arg(short = 'n', long = "line-number")]
     pub line_number: bool,
 
+    /// Show column numbers (and line numbers) of matches if set
+    #[arg(long = "column")]
+    pub column: bool,
+
+    /// Only find definitions of these kinds (function, class, etc.); can be repeated or
+    /// comma-separated
+    #[arg(short = 'k', long = "kind", value_delimiter = ',')]
+    pub kinds: Option<Vec<DefinitionKind>>,
+
+    /// Show the kind of each definition (function, class, etc.) if set
+    #[arg(long = "show-kind")]
+    pub show_kind: bool,
+
+    /// Also find `impl` blocks (rs) and extensions (swift and dart) that add to the symbol; these
+    /// are skipped by default because the symbol is defined elsewhere
+    #[arg(long = "include-impl")]
+    pub include_impl: bool,
+
     /// Disable color (also supports NO_COLOR env)
     #[arg(long = "no-color")]
     pub no_color: bool,
 
+    /// The output format (grep, json, ndjson, vimgrep, emacs); formats other than grep always
+    /// include positions and json and ndjson always include kinds
+    #[arg(long = "format")]
+    pub format: Option<OutputFormat>,
+
+    /// Print results for Vim's quickfix list; the same as `--format vimgrep`
+    #[arg(long = "vimgrep", conflicts_with = "format")]
+    pub vimgrep: bool,
+
+    /// The order of results (path, none, relevance); defaults to path
+    #[arg(long = "sort")]
+    pub sort: Option<SortBy>,
+
+    /// Stop searching after finding this many results
+    #[arg(short = 'm', long = "max-count")]
+    pub max_results: Option<usize>,
+
+    /// The file name to use for stdin, which also determines its file type if --type is not set
+    #[arg(long = "stdin-filename")]
+    pub stdin_filename: Option<String>,
+
+    /// Don't print warnings about files that could not be searched
+    #[arg(long = "no-messages")]
+    pub no_messages: bool,
+
     /// (Advanced) Print debugging information
     #[arg(long = "debug")]
     pub debug: bool,
@@ -115,6 +251,14 @@ pub struct Args {
 }
 
 impl Args {
+    /// Return the [OutputFormat] selected by these arguments
+    pub fn output_format(&self) -> OutputFormat {
+        if self.vimgrep {
+            return OutputFormat::Vimgrep;
+        }
+        self.format.clone().unwrap_or_default()
+    }
+
     /// Create a new set of arguments for [Searcher] with the minimal configuration
     pub fn from_query(query: &str) -> Args {
         Args {
@@ -132,7 +276,7 @@ impl Args {
     ) -> Args {
         Args {
             query,
-            file_type,
+            file_type: file_type.map(|file_type| vec![file_type]),
             file_path,
             line_number,
             ..Args::default()
@@ -144,19 +288,62 @@ impl Args {
 ///
 /// In general, a pre-scan is a good idea to quickly skip files that don't have a match, which
 /// should be most files. You shouldn't need to change this from the default.
+///
+/// Each file is always read fully into memory and decoded as text before it is scanned.
 #[derive(clap::ValueEnum, Clone, Default, Debug, EnumString, PartialEq, Display)]
 pub enum SearchMethod {
-    /// Pre-scan each file by reading fully into memory and using a Regex
+    /// Pre-scan each file using a Regex
     #[default]
     PrescanRegex,
 
-    /// Pre-scan each file by reading bytes until the query is found using memmem
+    /// Pre-scan each file for the query text using memmem
     PrescanMemmem,
 
     /// Don't pre-scan files.
     NoPrescan,
 }
 
+/// The format used to print each [SearchResult]
+///
+/// The structured formats are meant to be consumed by other programs like editor plugins.
+#[derive(clap::ValueEnum, Clone, Default, Debug, EnumString, PartialEq, Display)]
+pub enum OutputFormat {
+    /// One result per line as `file path:line number:text`; see [SearchResult::to_grep]
+    #[default]
+    Grep,
+
+    /// A single JSON array of all results; see [SearchResult::to_json]
+    Json,
+
+    /// One JSON object per line (newline-delimited JSON); see [SearchResult::to_json]
+    Ndjson,
+
+    /// One result per line for Vim's quickfix list; see [SearchResult::to_vimgrep]
+    Vimgrep,
+
+    /// One result per line for Emacs' compilation and grep modes; see [SearchResult::to_emacs]
+    Emacs,
+}
+
+/// The order in which results are returned
+///
+/// Files are searched in parallel, so only [SortBy::None] returns results in the order that each
+/// file happens to finish.
+#[derive(clap::ValueEnum, Clone, Default, Debug, EnumString, PartialEq, Display)]
+pub enum SortBy {
+    /// Sort by the order of the file paths searched, then by file name within each directory,
+    /// then by line number
+    #[default]
+    Path,
+
+    /// Don't sort; this can be slightly faster
+    None,
+
+    /// Sort symbols that exactly match the query first, then files closer to the top of the
+    /// directory tree, then the same as [SortBy::Path]
+    Relevance,
+}
+
 /// The configuration used by a [Searcher]
 ///
 /// Created by passing [Args] to [Config::new].
@@ -166,14 +353,35 @@ struct Config {
     query: String,
 
     /// The list of file paths to search, ignoring invisible or gitignored files
+    ///
+    /// A path of [STDIN_PATH] means stdin.
     file_paths: Vec<String>,
 
-    /// The type of files to scan (JS or PHP or RS)
-    file_type: FileType,
+    /// The file name to use for stdin, if any
+    stdin_filename: Option<String>,
+
+    /// The types of files to scan (JS or PHP or RS or PY or GO or JAVA or KT or C or RB or CS or
+    /// SWIFT or DART or SCALA)
+    file_types: Vec<FileType>,
+
+    /// Match the query without regard to case if true
+    ignore_case: bool,
 
     /// Include line numbers in results if true
     line_number: bool,
 
+    /// Include column numbers and byte offsets in results if true
+    column: bool,
+
+    /// Only find definitions of these kinds, or all kinds if not set
+    kinds: Option<Vec<DefinitionKind>>,
+
+    /// Include the kind of each definition in results if true
+    show_kind: bool,
+
+    /// Also treat `impl` blocks and extensions as definitions if true
+    include_impl: bool,
+
     /// Output debugging info during search if true
     debug: bool,
 
@@ -183,39 +391,74 @@ struct Config {
     /// The [SearchMethod] to use
     search_method: SearchMethod,
 
+    /// The [SortBy] order of results
+    sort: SortBy,
+
+    /// The maximum number of results to return, or all results if not set
+    max_results: Option<usize>,
+
     /// The number of threads to use for searching files
     num_threads: NonZero<usize>,
 }
 
 impl Config {
     /// Create a new Config using an [Args]
-    pub fn new(args: Args) -> Result<Config, String> {
+    pub fn new(args: Args) -> Result<Config, Error> {
         if args.debug {
             let args_formatted = format!("Creating config with args {:?}", args);
             println!("{}", args_formatted.yellow());
         }
+        // The query is put into every regex used to search, so it must be a valid regex itself.
+        if Regex::new(&args.query).is_err() {
+            return Err(Error::InvalidQuery(args.query));
+        }
+        let format = args.output_format();
+        let is_structured = matches!(format, OutputFormat::Json | OutputFormat::Ndjson);
         let file_paths = match args.file_path {
             Some(file_path) => file_path,
             None => vec![".".into()],
         };
-        let file_type = match args.file_type {
-            Some(file_type_string) => FileType::from_string(file_type_string.as_str())?,
-            None => FileType::from_file_paths(&file_paths)?,
+        let has_stdin = file_paths.iter().any(|file_path| file_path == STDIN_PATH);
+        let file_types = match args.file_type {
+            Some(file_type_strings) => FileType::from_strings(&file_type_strings)?,
+            None => match (has_stdin, &args.stdin_filename) {
+                (true, Some(stdin_filename)) => vec![FileType::from_file_name(stdin_filename)?],
+                (true, None) => return Err(Error::StdinFileTypeRequired),
+                (false, _) => vec![FileType::from_file_paths(&file_paths)?],
+            },
         };
+        // Without a file name, there's no way to tell which of several file types stdin is.
+        if has_stdin && args.stdin_filename.is_none() && file_types.len() > 1 {
+            return Err(Error::StdinFileTypeRequired);
+        }
 
         let num_threads = match args.threads {
             Some(threads) => threads,
             None => NonZero::new(5).expect("Default number of threads was invalid"),
         };
 
+        // Like ripgrep, smart case only applies when the query is all lowercase.
+        let ignore_case =
+            args.ignore_case || (args.smart_case && !args.query.chars().any(char::is_uppercase));
+
         let config = Config {
+            ignore_case,
             query: args.query,
             file_paths,
-            file_type,
-            line_number: args.line_number,
+            stdin_filename: args.stdin_filename,
+            file_types,
+            // Formats other than grep are for other programs, which will always want the position.
+            // Structured formats will always want the kind as well.
+            line_number: args.line_number || args.column || format != OutputFormat::Grep,
+            column: args.column || format != OutputFormat::Grep,
+            show_kind: args.show_kind || is_structured,
+            kinds: args.kinds,
+            include_impl: args.include_impl,
             debug: args.debug,
             no_color: args.no_color,
             search_method: args.search_method.unwrap_or_default(),
+            sort: args.sort.unwrap_or_default(),
+            max_results: args.max_results,
             num_threads,
         };
         debug(&config, format!("Created config {:?}", config).as_str());
@@ -227,7 +470,7 @@ impl Config {
 ///
 /// You can turn a string into a [FileType] using [FileType::from_string] which also supports
 /// type aliases like `javascript`, `javascriptreact`, or `typescript.tsx`.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, EnumIter)]
 pub enum FileType {
     /// The JS (or TS) file type
     JS,
@@ -237,6 +480,36 @@ pub enum FileType {
 
     /// The Rust file type
     RS,
+
+    /// The Python file type
+    PY,
+
+    /// The Go file type
+    GO,
+
+    /// The Java file type
+    JAVA,
+
+    /// The Kotlin file type
+    KT,
+
+    /// The C (or C++) file type
+    C,
+
+    /// The Ruby file type
+    RB,
+
+    /// The C# file type
+    CS,
+
+    /// The Swift file type
+    SWIFT,
+
+    /// The Dart file type
+    DART,
+
+    /// The Scala file type
+    SCALA,
 }
 
 impl FileType {
@@ -244,7 +517,7 @@ impl FileType {
     ///
     /// You can turn a string into a [FileType] using [FileType::from_string] which also supports
     /// type aliases like `javascript`, `javascriptreact`, or `typescript.tsx`.
-    pub fn from_string(file_type_string: &str) -> Result<FileType, String> {
+    pub fn from_string(file_type_string: &str) -> Result<FileType, Error> {
         match file_type_string {
             "js" => Ok(FileType::JS),
             "ts" => Ok(FileType::JS),
@@ -258,8 +531,63 @@ impl FileType {
             "typescriptreact" => Ok(FileType::JS),
             "php" => Ok(FileType::PHP),
             "rs" => Ok(FileType::RS),
-            _ => Err(format!("Invalid file type '{}'", file_type_string)),
+            "py" => Ok(FileType::PY),
+            "pyi" => Ok(FileType::PY),
+            "python" => Ok(FileType::PY),
+            "go" => Ok(FileType::GO),
+            "golang" => Ok(FileType::GO),
+            "java" => Ok(FileType::JAVA),
+            "kt" => Ok(FileType::KT),
+            "kts" => Ok(FileType::KT),
+            "kotlin" => Ok(FileType::KT),
+            "c" => Ok(FileType::C),
+            "h" => Ok(FileType::C),
+            "cc" => Ok(FileType::C),
+            "cpp" => Ok(FileType::C),
+            "cxx" => Ok(FileType::C),
+            "hpp" => Ok(FileType::C),
+            "hh" => Ok(FileType::C),
+            "c++" => Ok(FileType::C),
+            "rb" => Ok(FileType::RB),
+            "rake" => Ok(FileType::RB),
+            "ruby" => Ok(FileType::RB),
+            "cs" => Ok(FileType::CS),
+            "csharp" => Ok(FileType::CS),
+            "c#" => Ok(FileType::CS),
+            "swift" => Ok(FileType::SWIFT),
+            "dart" => Ok(FileType::DART),
+            "scala" => Ok(FileType::SCALA),
+            "sc" => Ok(FileType::SCALA),
+            _ => Err(Error::UnknownFileType(file_type_string.into())),
+        }
+    }
+
+    /// Turn a list of strings into a list of [FileType]
+    ///
+    /// Each string is handled by [FileType::from_string] except for the special value `all`,
+    /// which selects every supported [FileType]. Duplicates are removed.
+    pub fn from_strings(file_type_strings: &[String]) -> Result<Vec<FileType>, Error> {
+        let mut file_types: Vec<FileType> = vec![];
+        for file_type_string in file_type_strings {
+            let new_file_types = match file_type_string.as_str() {
+                "all" => FileType::iter().collect(),
+                _ => vec![FileType::from_string(file_type_string)?],
+            };
+            for file_type in new_file_types {
+                if !file_types.contains(&file_type) {
+                    file_types.push(file_type);
+                }
+            }
         }
+        Ok(file_types)
+    }
+
+    /// Try to guess a [FileType] based on the name of a single file
+    ///
+    /// Unlike [FileType::from_file_paths], the file does not need to exist.
+    pub fn from_file_name(file_name: &str) -> Result<FileType, Error> {
+        file_type::guess_file_type_from_file_name(file_name)
+            .ok_or_else(|| Error::CannotGuessFileType(vec![file_name.into()]))
     }
 
     /// Try to guess a [FileType] based on a list of file paths
@@ -270,30 +598,99 @@ impl FileType {
     ///
     /// If a directory includes multiple supported file types, this could be incorrect, so it's
     /// more reliable (and faster) to specify a file type explicitly.
-    pub fn from_file_paths(file_paths: &Vec<String>) -> Result<FileType, &'static str> {
+    pub fn from_file_paths(file_paths: &Vec<String>) -> Result<FileType, Error> {
         for file_path in file_paths {
             let guess = file_type::guess_file_type_from_file_path(file_path);
             if let Some(value) = guess {
                 return Ok(value);
             }
         }
-        Err("Unable to guess file type from file paths")
+        Err(Error::CannotGuessFileType(file_paths.clone()))
     }
 }
 
+/// The kind of symbol that a definition creates
+///
+/// Each [FileType] maps its own syntax onto these kinds as closely as it can; for example, a Rust
+/// `union` is a [DefinitionKind::Struct] and a PHP `define()` is a [DefinitionKind::Const].
+#[derive(clap::ValueEnum, Clone, Debug, EnumString, PartialEq, Display, Serialize)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum DefinitionKind {
+    /// A function that is not part of a class or similar type
+    Function,
+
+    /// A function that is part of a class or similar type
+    Method,
+
+    /// A class
+    Class,
+
+    /// A struct (or union)
+    Struct,
+
+    /// A trait
+    Trait,
+
+    /// An interface
+    Interface,
+
+    /// An enum
+    Enum,
+
+    /// A type alias or type definition
+    Type,
+
+    /// A constant
+    Const,
+
+    /// A variable
+    Variable,
+
+    /// A module or namespace
+    Module,
+
+    /// A property of an object or class
+    Property,
+
+    /// A macro
+    Macro,
+
+    /// An implementation block or extension for a type
+    Impl,
+}
+
 /// A result from calling [Searcher::search]
 ///
 /// The `line_number` will be set only if [Args::line_number] is true when calling [Searcher::search].
+/// Likewise, the `column` and `byte_offset` will be set only if [Args::column] is true and the
+/// `kind` will be set only if [Args::show_kind] is true.
 ///
 /// See [SearchResult::to_grep] as the most common formatting output.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize)]
 pub struct SearchResult {
     /// The path to the file containing the symbol definition
-    pub file_path: String,
+    ///
+    /// File names that are not valid UTF-8 are kept as they are, but any invalid characters are
+    /// replaced when the result is formatted or serialized.
+    #[serde(serialize_with = "serialize_path_lossy")]
+    pub file_path: PathBuf,
 
     /// The line number of the symbol definition in the file
     pub line_number: Option<usize>,
 
+    /// The column of the symbol name on its line, counted in bytes starting from 1
+    pub column: Option<usize>,
+
+    /// The position of the symbol name in the file, counted in bytes starting from 0
+    pub byte_offset: Option<usize>,
+
+    /// The symbol name as it appears in the definition
+    pub symbol: String,
+
+    /// The kind of definition
+    pub kind: Option<DefinitionKind>,
+
     /// The symbol definition line
     pub text: String,
 }
@@ -302,7 +699,9 @@ impl SearchResult {
     /// Return a formatted string for output in the "grep" format
     ///
     /// That is, either `file path:text on line` or, if [Args::line_number] is true,
-    /// `file path:line number:text on line`.
+    /// `file path:line number:text on line`. If [Args::column] is true, the column is added after
+    /// the line number, like `file path:line number:column:text on line`. If [Args::show_kind] is
+    /// true, the kind is added before the text, like `file path:line number:kind:text on line`.
     ///
     /// # Example
     ///
@@ -312,27 +711,127 @@ impl SearchResult {
     /// ./src/queries.js:17:function parseQuery {
     /// ```
     pub fn to_grep(&self) -> String {
-        match self.line_number {
-            Some(line_number) => format!(
+        let file_path = self.file_path.to_string_lossy();
+        let text = match &self.kind {
+            Some(kind) => format!("{}:{}", kind.to_string().cyan(), self.text),
+            None => self.text.clone(),
+        };
+        match (self.line_number, self.column) {
+            (Some(line_number), Some(column)) => format!(
+                "{}:{}:{}:{}",
+                file_path.magenta(),
+                line_number.to_string().green(),
+                column.to_string().green(),
+                text
+            ),
+            (Some(line_number), None) => format!(
                 "{}:{}:{}",
-                self.file_path.magenta(),
+                file_path.magenta(),
                 line_number.to_string().green(),
-                self.text
+                text
             ),
-            None => format!("{}:{}", self.file_path.magenta(), self.text),
+            _ => format!("{}:{}", file_path.magenta(), text),
+        }
+    }
+
+    /// Return a formatted string for output in the "vimgrep" format used by Vim's quickfix list
+    ///
+    /// That is, `file path:line number:column:text on line`, like `ripgrep --vimgrep`. This is
+    /// never colored. If the line number or column were not included in the search, they will be
+    /// `1`.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// ./src/queries.js:17:10:function parseQuery {
+    /// ```
+    pub fn to_vimgrep(&self) -> String {
+        format!(
+            "{}:{}:{}:{}",
+            self.file_path.to_string_lossy(),
+            self.line_number.unwrap_or(1),
+            self.column.unwrap_or(1),
+            self.text
+        )
+    }
+
+    /// Return a formatted string for output in the format used by Emacs' compilation mode
+    ///
+    /// That is, `file path:line number:column: text on line`, which is recognized by
+    /// `compilation-mode`, `grep-mode`, and commands built on them. This is never colored. If the
+    /// line number or column were not included in the search, they will be `1`.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// ./src/queries.js:17:10: function parseQuery {
+    /// ```
+    pub fn to_emacs(&self) -> String {
+        format!(
+            "{}:{}:{}: {}",
+            self.file_path.to_string_lossy(),
+            self.line_number.unwrap_or(1),
+            self.column.unwrap_or(1),
+            self.text
+        )
+    }
+
+    /// Return a JSON object string for the result
+    ///
+    /// This is used for both [OutputFormat::Json] and [OutputFormat::Ndjson].
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// {"file_path":"./src/queries.js","line_number":17,"column":10,"byte_offset":321,"symbol":"parseQuery","kind":"function","text":"function parseQuery {"}
+    /// ```
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Unable to serialize search result")
+    }
+}
+
+/// A problem with one file that was skipped during a search
+///
+/// These don't stop the search; they can be read with [Searcher::warnings] when it is done.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SearchWarning {
+    /// The path to the file or directory that could not be searched
+    pub file_path: PathBuf,
+
+    /// The reason that the file could not be searched
+    pub message: String,
+}
+
+impl SearchWarning {
+    fn new(file_path: &Path, message: &str) -> SearchWarning {
+        SearchWarning {
+            file_path: file_path.into(),
+            message: message.into(),
+        }
+    }
+
+    /// Create a [SearchWarning] from an error of the directory walker, which may know the exact
+    /// path that failed
+    fn from_walk_error(file_path: &Path, error: &ignore::Error) -> SearchWarning {
+        match error {
+            ignore::Error::WithPath { path, err } => SearchWarning::from_walk_error(path, err),
+            ignore::Error::WithDepth { err, .. } | ignore::Error::WithLineNumber { err, .. } => {
+                SearchWarning::from_walk_error(file_path, err)
+            }
+            // The walker wraps its IO errors in another error whose message repeats the path.
+            ignore::Error::Io(err) => match err.get_ref().and_then(|inner| inner.source()) {
+                Some(source) => SearchWarning::new(file_path, &source.to_string()),
+                None => SearchWarning::new(file_path, &err.to_string()),
+            },
+            _ => SearchWarning::new(file_path, &error.to_string()),
         }
     }
 }
 
-fn get_regexp_for_query(query: &str, file_type: &FileType) -> Regex {
-    let regexp_string = match file_type {
-        FileType::JS => &format!(
-            r"(\b(function|var|let|const|class|interface|type)\s+{query}\b|\b{query}\([^)]*\)\s*(:[^\{{]+)?\{{|\b{query}:|@typedef\s*(\{{[^\}}]+\}})?\s*{query}\b)"
-        ),
-        FileType::PHP => &format!(r"\b(function|class|trait|interface|enum) {query}\b"),
-        FileType::RS => &format!(r"\b(fn|trait|enum|struct|mod) {query}\b"),
-    };
-    Regex::new(regexp_string).expect("Could not create regex for file type query")
+impl std::fmt::Display for SearchWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.file_path.display(), self.message)
+    }
 }
 
 /// A struct that can perform a search
@@ -354,30 +853,89 @@ fn get_regexp_for_query(query: &str, file_type: &FileType) -> Regex {
 ///     println!("{}", result.to_grep());
 /// }
 /// ```
+///
+/// To handle each result as soon as it is found rather than waiting for the whole search to
+/// finish, use [Searcher::search_with].
+///
+/// ```
+/// use grepdef::{Args, Searcher};
+/// let searcher = Searcher::new(Args::from_query("parseQuery")).unwrap();
+/// searcher
+///     .search_with(|result| println!("{}", result.to_grep()))
+///     .unwrap();
+/// ```
+///
+/// Files that cannot be read do not stop the search; use [Searcher::warnings] afterward to find
+/// out which files were skipped and why.
 pub struct Searcher {
     config: Config,
+
+    /// The problems found by the most recent search, with the order of the file in the walk
+    warnings: Mutex<Vec<(usize, SearchWarning)>>,
 }
 
 impl Searcher {
     /// Create a new Config using an [Args]
-    pub fn new(args: Args) -> Result<Searcher, String> {
+    pub fn new(args: Args) -> Result<Searcher, Error> {
         let config = Config::new(args)?;
-        Ok(Searcher { config })
+        Ok(Searcher {
+            config,
+            warnings: Mutex::new(vec![]),
+        })
     }
 
     /// Perform the search this struct was built to do
-    pub fn search(&self) -> Result<Vec<SearchResult>, Box<dyn Error>> {
+    pub fn search(&self) -> Result<Vec<SearchResult>, Error> {
+        let mut results = vec![];
+        self.search_with(|result| results.push(result))?;
+        Ok(results)
+    }
+
+    /// Perform the search this struct was built to do, calling `callback` with each result
+    ///
+    /// Results are passed to the callback as soon as they can be, while other files are still
+    /// being searched. With [SortBy::Path], a file's results wait only for the files before it;
+    /// with [SortBy::Relevance], all results wait until the search is complete.
+    ///
+    /// If [Args::max_results] is set, the search stops as soon as that many results have been
+    /// passed to the callback. With [SortBy::Relevance] every file must still be searched.
+    ///
+    /// Paths that cannot be searched, including any of [Args::file_path] that do not exist, do not
+    /// stop the search; they are available from [Searcher::warnings] instead.
+    pub fn search_with<F>(&self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(SearchResult),
+    {
         // Don't try to even calculate elapsed time if we are not going to print it
         let start: Option<time::Instant> = if self.config.debug {
             Some(time::Instant::now())
         } else {
             None
         };
-        let re = get_regexp_for_query(&self.config.query, &self.config.file_type);
-        let file_type_re = file_type::get_regexp_for_file_type(&self.config.file_type);
+        // Each file is searched with the query regexes for the first file type whose file name
+        // pattern it matches.
+        // File types which cannot define any of the requested kinds are not searched at all.
+        let file_type_regexps: Vec<(Regex, QueryRegexes)> = self
+            .config
+            .file_types
+            .iter()
+            .filter_map(|file_type| {
+                let regexes = QueryRegexes::new(&self.config, file_type)?;
+                Some((file_type::get_regexp_for_file_type(file_type), regexes))
+            })
+            .collect();
         let mut pool = threads::ThreadPool::new(self.config.num_threads);
-        let results: Vec<SearchResult> = vec![];
-        let results = Arc::new(Mutex::new(results));
+        // Each file's results are sent with the order in which the file was found so that they
+        // can be sorted as they arrive.
+        let (sender, receiver) = mpsc::channel::<FileSearch>();
+        let mut sorter = ResultSorter::new(&self.config, callback);
+        // Warnings are stored only once the search is done so that the callback can't block
+        // while trying to read them.
+        let mut warnings: Vec<(usize, SearchWarning)> = vec![];
+        self.warnings
+            .lock()
+            .expect("Unable to clear search warnings: mutex failed")
+            .clear();
 
         if self.config.no_color {
             colored::control::set_override(false);
@@ -385,51 +943,74 @@ impl Searcher {
 
         self.debug("Starting searchers");
         let mut searched_file_count = 0;
-        for file_path in &self.config.file_paths {
-            for entry in Walk::new(file_path) {
-                let path = entry?.into_path();
-                if path.is_dir() {
-                    continue;
+        'walk: for file_path in &self.config.file_paths {
+            if file_path == STDIN_PATH {
+                if let Some(regexes) = self.get_stdin_regexes(&file_type_regexps) {
+                    let file_index = searched_file_count;
+                    searched_file_count += 1;
+                    self.execute_search(&pool, &sender, regexes, SearchInput::Stdin, file_index);
                 }
-                let path = match path.to_str() {
-                    Some(p) => p.to_string(),
-                    None => return Err("Error getting string from path".into()),
+                continue;
+            }
+            for entry in self.walk(file_path) {
+                // Like grep, a path that can't be read (even one given to search) is skipped so that
+                // the rest of the paths are still searched.
+                let path = match entry {
+                    Ok(entry) => entry.into_path(),
+                    Err(source) => {
+                        let warning = SearchWarning::from_walk_error(Path::new(file_path), &source);
+                        warnings.push((searched_file_count, warning));
+                        continue;
+                    }
                 };
-                if !file_type_re.is_match(&path) {
+                if path.is_dir() {
                     continue;
                 }
+                // File names that are not valid UTF-8 can still be matched by their extension.
+                let path_string = path.to_string_lossy();
+                let regexes = match file_type_regexps
+                    .iter()
+                    .find(|(file_type_re, _)| file_type_re.is_match(&path_string))
+                {
+                    Some((_, regexes)) => regexes,
+                    None => continue,
+                };
+                let file_index = searched_file_count;
                 searched_file_count += 1;
+                self.execute_search(&pool, &sender, regexes, SearchInput::File(path), file_index);
 
-                let re1 = re.clone();
-                let path1 = path.clone();
-                let config1 = self.config.clone();
-                let results1 = Arc::clone(&results);
-                pool.execute(move || {
-                    search_file(
-                        &re1,
-                        &path1,
-                        &config1,
-                        move |file_results: Vec<SearchResult>| {
-                            results1
-                                .lock()
-                                .expect("Unable to collect search data from thread")
-                                .extend(file_results);
-                        },
-                    );
-                })
+                // Pass along any results that are ready without waiting for the walk to finish.
+                for file_search in receiver.try_iter() {
+                    file_search.add_to(&mut sorter, &mut warnings);
+                }
+                if sorter.is_full() {
+                    self.debug("Found maximum results; cancelling searchers");
+                    pool.cancel();
+                    break 'walk;
+                }
             }
         }
 
         self.debug("Waiting for searchers to complete");
+        // Once every job has finished and dropped its copy of the sender, the receiver will stop.
+        drop(sender);
+        for file_search in receiver {
+            file_search.add_to(&mut sorter, &mut warnings);
+            if sorter.is_full() {
+                pool.cancel();
+            }
+        }
         pool.wait_for_all_jobs_and_stop();
+        sorter.finish();
+        if self.config.sort != SortBy::None {
+            warnings.sort_by_key(|(file_index, _)| *file_index);
+        }
+        *self
+            .warnings
+            .lock()
+            .expect("Unable to collect search warnings: mutex failed") = warnings;
         self.debug("Searchers complete");
 
-        let results = Arc::into_inner(results)
-            .expect("Unable to collect search results from threads: reference counter failed");
-        let results = results
-            .into_inner()
-            .expect("Unable to collect search results from threads: mutex failed");
-
         // Don't try to even calculate elapsed time if we are not going to print it
         if let (true, Some(start)) = (self.config.debug, start) {
             self.debug(
@@ -441,7 +1022,70 @@ impl Searcher {
                 .as_str(),
             );
         }
-        Ok(results)
+        Ok(())
+    }
+
+    /// Search one file (or stdin) using a thread from the pool and send its results to `sender`
+    fn execute_search(
+        &self,
+        pool: &threads::ThreadPool,
+        sender: &mpsc::Sender<FileSearch>,
+        regexes: &QueryRegexes,
+        input: SearchInput,
+        file_index: usize,
+    ) {
+        let regexes1 = regexes.clone();
+        let config1 = self.config.clone();
+        let sender1 = sender.clone();
+        pool.execute(move || {
+            let callback = move |results: Vec<SearchResult>, warnings: Vec<SearchWarning>| {
+                // The receiver only goes away if the search has already failed.
+                let _ = sender1.send(FileSearch {
+                    file_index,
+                    results,
+                    warnings,
+                });
+            };
+            match input {
+                SearchInput::File(path) => search_file(&regexes1, &path, &config1, callback),
+                SearchInput::Stdin => search_stdin(&regexes1, &config1, callback),
+            }
+        });
+    }
+
+    /// Return the regexes to use for stdin, if it has a file type that is being searched
+    fn get_stdin_regexes<'a>(
+        &self,
+        file_type_regexps: &'a [(Regex, QueryRegexes)],
+    ) -> Option<&'a QueryRegexes> {
+        // A single file type is used for stdin no matter what its file name is.
+        if self.config.file_types.len() == 1 {
+            return file_type_regexps.first().map(|(_, regexes)| regexes);
+        }
+        let stdin_filename = self.config.stdin_filename.as_ref()?;
+        file_type_regexps
+            .iter()
+            .find(|(file_type_re, _)| file_type_re.is_match(stdin_filename))
+            .map(|(_, regexes)| regexes)
+    }
+
+    /// Return the problems with files that were skipped by the most recent search
+    pub fn warnings(&self) -> Vec<SearchWarning> {
+        self.warnings
+            .lock()
+            .expect("Unable to read search warnings: mutex failed")
+            .iter()
+            .map(|(_, warning)| warning.clone())
+            .collect()
+    }
+
+    /// Return a directory walker whose order is stable unless [SortBy::None] is used
+    fn walk(&self, file_path: &str) -> Walk {
+        let mut builder = WalkBuilder::new(file_path);
+        if self.config.sort != SortBy::None {
+            builder.sort_by_file_name(|a, b| a.cmp(b));
+        }
+        builder.build()
     }
 
     fn debug(&self, output: &str) {
@@ -451,93 +1095,270 @@ impl Searcher {
     }
 }
 
+/// The file path that means stdin when passed to [Args::file_path]
+pub const STDIN_PATH: &str = "-";
+
+/// Where the text for one search thread comes from
+enum SearchInput {
+    File(PathBuf),
+    Stdin,
+}
+
+/// The results and warnings from searching one file, sent from a search thread
+struct FileSearch {
+    /// The order in which the file was found in the walk
+    file_index: usize,
+    results: Vec<SearchResult>,
+    warnings: Vec<SearchWarning>,
+}
+
+impl FileSearch {
+    fn add_to<F: FnMut(SearchResult)>(
+        self,
+        sorter: &mut ResultSorter<F>,
+        warnings: &mut Vec<(usize, SearchWarning)>,
+    ) {
+        let file_index = self.file_index;
+        warnings.extend(
+            self.warnings
+                .into_iter()
+                .map(|warning| (file_index, warning)),
+        );
+        sorter.add(file_index, self.results);
+    }
+}
+
+/// Passes the results of each file to a callback in the [SortBy] order as soon as it can
+struct ResultSorter<F: FnMut(SearchResult)> {
+    sort: SortBy,
+    query: String,
+    ignore_case: bool,
+    callback: F,
+
+    /// The number of results that can still be passed to the callback, if there is a maximum
+    remaining: Option<usize>,
+
+    /// The order of the next file whose results can be passed to the callback
+    next_file_index: usize,
+
+    /// The results of files that are waiting for an earlier file, keyed by the order of the file
+    pending: BTreeMap<usize, Vec<SearchResult>>,
+}
+
+impl<F: FnMut(SearchResult)> ResultSorter<F> {
+    fn new(config: &Config, callback: F) -> ResultSorter<F> {
+        ResultSorter {
+            sort: config.sort.clone(),
+            query: config.query.clone(),
+            ignore_case: config.ignore_case,
+            callback,
+            remaining: config.max_results,
+            next_file_index: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Add the results of the file found at `file_index` in the walk
+    fn add(&mut self, file_index: usize, file_results: Vec<SearchResult>) {
+        match self.sort {
+            SortBy::None => self.emit(file_results),
+            SortBy::Path => {
+                self.pending.insert(file_index, file_results);
+                while let Some(file_results) = self.pending.remove(&self.next_file_index) {
+                    self.next_file_index += 1;
+                    self.emit(file_results);
+                }
+            }
+            SortBy::Relevance => {
+                self.pending.insert(file_index, file_results);
+            }
+        }
+    }
+
+    /// Return true if no more results can be passed to the callback
+    fn is_full(&self) -> bool {
+        self.remaining == Some(0)
+    }
+
+    /// Pass any results that are still waiting to the callback
+    fn finish(mut self) {
+        let mut results: Vec<SearchResult> = std::mem::take(&mut self.pending)
+            .into_values()
+            .flatten()
+            .collect();
+        if self.sort == SortBy::Relevance {
+            // This is a stable sort so results that are equally relevant stay in path order.
+            results.sort_by_key(|result| {
+                (
+                    !self.is_exact_match(&result.symbol),
+                    get_path_depth(&result.file_path),
+                )
+            });
+        }
+        self.emit(results);
+    }
+
+    /// Return true if the symbol is the query itself, ignoring case if the search does
+    fn is_exact_match(&self, symbol: &str) -> bool {
+        if self.ignore_case {
+            return symbol.to_lowercase() == self.query.to_lowercase();
+        }
+        symbol == self.query
+    }
+
+    /// Pass results to the callback until the maximum is reached
+    fn emit(&mut self, results: Vec<SearchResult>) {
+        for result in results {
+            if let Some(remaining) = self.remaining.as_mut() {
+                if *remaining == 0 {
+                    return;
+                }
+                *remaining -= 1;
+            }
+            (self.callback)(result);
+        }
+    }
+}
+
+fn get_path_depth(file_path: &Path) -> usize {
+    file_path
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .count()
+}
+
+/// Serialize a path as a string even if it is not valid UTF-8
+fn serialize_path_lossy<S: Serializer>(path: &Path, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&path.to_string_lossy())
+}
+
 fn debug(config: &Config, output: &str) {
     if config.debug {
         println!("{}", output.yellow());
     }
 }
 
-fn search_file<F>(re: &Regex, file_path: &str, config: &Config, callback: F)
+fn search_file<F>(regexes: &QueryRegexes, file_path: &Path, config: &Config, callback: F)
 where
-    F: FnOnce(Vec<SearchResult>) + Send + 'static,
+    F: FnOnce(Vec<SearchResult>, Vec<SearchWarning>) + Send + 'static,
 {
-    debug(config, format!("Scanning file {}", file_path).as_str());
-    let file = fs::File::open(file_path);
-
-    match file {
-        Ok(mut file) => {
-            // Scan the file in big chunks to see if it has what we are looking for. This is more efficient
-            // than going line-by-line on every file since matches should be quite rare.
-            if match config.search_method {
-                SearchMethod::PrescanRegex => !file_type::does_file_match_regexp(&file, re),
-                SearchMethod::PrescanMemmem => {
-                    !file_type::does_file_match_query(&file, &config.query)
-                }
-                SearchMethod::NoPrescan => false,
-            } {
-                debug(
-                    config,
-                    format!("Presearch of {} found no match; skipping", &file_path).as_str(),
-                );
-                callback(vec![]);
-                return;
-            }
+    debug(
+        config,
+        format!("Scanning file {}", file_path.display()).as_str(),
+    );
+    search_contents(regexes, file_path, fs::read(file_path), config, callback);
+}
 
-            let rewind_result = file.rewind();
-            if rewind_result.is_err() {
-                callback(vec![]);
-                return;
-            }
-            debug(
-                config,
-                format!(
-                    "Presearch of {} was successful; searching for line",
-                    &file_path
-                )
-                .as_str(),
+fn search_stdin<F>(regexes: &QueryRegexes, config: &Config, callback: F)
+where
+    F: FnOnce(Vec<SearchResult>, Vec<SearchWarning>) + Send + 'static,
+{
+    debug(config, "Scanning stdin");
+    let file_path = PathBuf::from(config.stdin_filename.as_deref().unwrap_or("<stdin>"));
+    let mut bytes = vec![];
+    let contents = io::stdin().read_to_end(&mut bytes).map(|_| bytes);
+    search_contents(regexes, &file_path, contents, config, callback);
+}
+
+fn search_contents<F>(
+    regexes: &QueryRegexes,
+    file_path: &Path,
+    contents: io::Result<Vec<u8>>,
+    config: &Config,
+    callback: F,
+) where
+    F: FnOnce(Vec<SearchResult>, Vec<SearchWarning>) + Send + 'static,
+{
+    let decoded = match contents {
+        Ok(bytes) => file_type::decode_text(bytes),
+        Err(err) => {
+            callback(
+                vec![],
+                vec![SearchWarning::new(file_path, &err.to_string())],
             );
-            callback(search_file_line_by_line(re, file_path, &file, config));
+            return;
+        }
+    };
+
+    // Scan the whole file to see if it has what we are looking for. This is more efficient than
+    // going line-by-line on every file since matches should be quite rare.
+    if !match config.search_method {
+        SearchMethod::PrescanRegex => {
+            file_type::does_text_match_regexp(&decoded.text, &regexes.prescan)
         }
-        Err(_) => {
-            callback(vec![]);
+        SearchMethod::PrescanMemmem => {
+            file_type::does_text_match_query(&decoded.text, &config.query, config.ignore_case)
         }
+        SearchMethod::NoPrescan => true,
+    } {
+        debug(
+            config,
+            format!(
+                "Presearch of {} found no match; skipping",
+                file_path.display()
+            )
+            .as_str(),
+        );
+        callback(vec![], vec![]);
+        return;
     }
+
+    debug(
+        config,
+        format!(
+            "Presearch of {} was successful; searching for line",
+            file_path.display()
+        )
+        .as_str(),
+    );
+    callback(
+        search_file_line_by_line(regexes, file_path, &decoded, config),
+        vec![],
+    );
 }
 
 fn search_file_line_by_line(
-    re: &Regex,
-    file_path: &str,
-    file: &fs::File,
+    regexes: &QueryRegexes,
+    file_path: &Path,
+    decoded: &DecodedText,
     config: &Config,
 ) -> Vec<SearchResult> {
-    let lines = io::BufReader::new(file).lines();
+    // Lines are split on the newline alone so that the byte offset of each line is known.
+    let lines = decoded.text.split('\n');
     let mut line_counter = 0;
+    let mut line_offset = 0;
+    let mut state = LineState::default();
 
     lines
         .filter_map(|line| {
             line_counter += 1;
-            if !match &line {
-                Ok(line) => re.is_match(line),
-                Err(_) => false,
-            } {
-                return None;
-            }
-
-            let text = match line {
-                Ok(line) => line,
-                // If reading the line causes an error (eg: invalid UTF), then skip it by treating
-                // it as empty.
-                Err(_err) => String::from(""),
+            let line_start = line_offset;
+            line_offset += line.len() + 1;
+            let (kind, definition) = regexes.find_definition(line, &mut state)?;
+            let (symbol_start, symbol) = regexes
+                .find_symbol(&definition)
+                .unwrap_or((definition.start(), &config.query));
+            // Positions are counted in the file's bytes, which may differ from the decoded text.
+            let position = if config.column {
+                let file_line_start = decoded.get_file_offset(line_start);
+                let file_symbol_start = decoded.get_file_offset(line_start + symbol_start);
+                Some((file_symbol_start - file_line_start + 1, file_symbol_start))
+            } else {
+                None
             };
 
             Some(SearchResult {
-                file_path: String::from(file_path),
+                file_path: file_path.to_path_buf(),
                 line_number: if config.line_number {
                     Some(line_counter)
                 } else {
                     None
                 },
-                text: text.trim().into(),
+                column: position.map(|(column, _)| column),
+                byte_offset: position.map(|(_, byte_offset)| byte_offset),
+                symbol: String::from(symbol),
+                kind: if config.show_kind { Some(kind) } else { None },
+                text: line.trim().into(),
             })
         })
         .collect()
@@ -1,22 +1,61 @@
 use clap::Parser;
 use grepdef::Args;
+use grepdef::OutputFormat;
 use grepdef::Searcher;
 use std::process;
 
+// These match the exit codes of grep.
+const EXIT_FOUND: i32 = 0;
+const EXIT_NOT_FOUND: i32 = 1;
+const EXIT_ERROR: i32 = 2;
+
 fn main() {
-    let searcher = Searcher::new(Args::parse()).unwrap_or_else(|err| {
+    let args = Args::parse();
+    let format = args.output_format();
+    let no_messages = args.no_messages;
+    let searcher = Searcher::new(args).unwrap_or_else(|err| {
         eprintln!("{err}");
-        process::exit(exitcode::USAGE);
+        process::exit(EXIT_ERROR);
     });
-    match searcher.search() {
-        Ok(results) => {
-            for line in results {
-                println!("{}", line.to_grep());
+    if format == OutputFormat::Json {
+        print!("[");
+    }
+    let mut result_count = 0;
+    let search_result = searcher.search_with(|result| {
+        match format {
+            OutputFormat::Grep => println!("{}", result.to_grep()),
+            OutputFormat::Json => {
+                if result_count > 0 {
+                    print!(",");
+                }
+                print!("{}", result.to_json());
             }
+            OutputFormat::Ndjson => println!("{}", result.to_json()),
+            OutputFormat::Vimgrep => println!("{}", result.to_vimgrep()),
+            OutputFormat::Emacs => println!("{}", result.to_emacs()),
+        };
+        result_count += 1;
+    });
+    if format == OutputFormat::Json {
+        println!("]");
+    }
+    if let Err(err) = search_result {
+        eprintln!("{err}");
+        process::exit(EXIT_ERROR);
+    }
+    let warnings = searcher.warnings();
+    if !no_messages {
+        for warning in &warnings {
+            eprintln!("{warning}");
         }
-        Err(err) => {
-            eprintln!("{err}");
-            process::exit(exitcode::USAGE);
-        }
-    };
+    }
+    // Like grep, skipping any file is an error even if definitions were found in other files.
+    if !warnings.is_empty() {
+        process::exit(EXIT_ERROR);
+    }
+    process::exit(if result_count > 0 {
+        EXIT_FOUND
+    } else {
+        EXIT_NOT_FOUND
+    });
 }
@@ -0,0 +1,748 @@
+use super::{Config, DefinitionKind, FileType};
+use regex::{Match, Regex};
+
+/// Regex strings paired with the [DefinitionKind] that each one finds
+type DefinitionPatterns = Vec<(DefinitionKind, String)>;
+
+/// Regexes used to find definitions that can only be recognized by the block they are in
+///
+/// For example, the constants inside a Go `const ( ... )` block have no keyword on their line.
+#[derive(Clone, Debug)]
+struct BlockRegexes {
+    /// Matches the line that opens the block and the kind of definitions inside it
+    starts: Vec<(DefinitionKind, Regex)>,
+
+    /// Matches the line that closes the block
+    end: Regex,
+
+    /// Matches a definition line inside the block
+    item: Regex,
+}
+
+/// Regexes used to find definitions whose name starts the line after their return type
+///
+/// For example, GNU style C puts the return type of a function on its own line before the name.
+#[derive(Clone, Debug)]
+struct SplitRegexes {
+    /// Matches a line that holds nothing but a return type
+    return_type: Regex,
+
+    /// Matches a line that starts with a definition if it follows a return type
+    names: Vec<(DefinitionKind, Regex)>,
+}
+
+/// What [QueryRegexes::find_definition] remembers about the lines it has already seen
+#[derive(Debug, Default)]
+pub struct LineState {
+    /// The kind of definitions inside the block recognized by [BlockRegexes], if the current
+    /// line is inside one
+    block_kind: Option<DefinitionKind>,
+
+    /// How deeply the current line is nested in brackets inside that block; definitions are only
+    /// found at the block's own level so that things like struct fields are skipped
+    block_depth: usize,
+
+    /// True if the previous line was matched by [SplitRegexes::return_type]
+    after_return_type: bool,
+}
+
+/// The regexes used to find definitions of a query in one [FileType]
+#[derive(Clone, Debug)]
+pub struct QueryRegexes {
+    /// Matches a definition on any single line; each pattern is in a group named by its index
+    line: Regex,
+
+    /// The [DefinitionKind] of each pattern in `line`
+    kinds: Vec<DefinitionKind>,
+
+    /// Matches definitions which depend on the block they are in, if the file type has any
+    block: Option<BlockRegexes>,
+
+    /// Matches definitions which are split across lines, if the file type has any
+    split: Option<SplitRegexes>,
+
+    /// Matches a line that is a statement and so cannot be a definition, if the file type has
+    /// statements that would otherwise look like one (like `return query(a,`)
+    statement: Option<Regex>,
+
+    /// Matches anything that might be a definition; used to scan whole files at once
+    pub prescan: Regex,
+
+    /// Matches the symbol name within a definition
+    symbol: Regex,
+}
+
+impl QueryRegexes {
+    /// Create the regexes for a [FileType], or return None if it has no patterns for the kinds
+    /// in [Config]
+    pub fn new(config: &Config, file_type: &FileType) -> Option<QueryRegexes> {
+        let query = &get_query_pattern(config);
+        let patterns =
+            filter_patterns_by_kind(config, get_definition_patterns_for_query(config, file_type));
+        if patterns.is_empty() {
+            return None;
+        }
+        let line_string = format!(
+            "(?m){}",
+            patterns
+                .iter()
+                .enumerate()
+                .map(|(index, (_, pattern))| format!("(?P<d{index}>{pattern})"))
+                .collect::<Vec<String>>()
+                .join("|")
+        );
+        let kinds = patterns.into_iter().map(|(kind, _)| kind).collect();
+        let block_patterns = get_block_patterns_for_query(query, file_type)
+            .map(|(starts, end, item)| (filter_patterns_by_kind(config, starts), end, item))
+            .filter(|(starts, _, _)| !starts.is_empty());
+        let split_patterns = get_split_patterns_for_query(query, file_type)
+            .map(|(return_type, names)| (return_type, filter_patterns_by_kind(config, names)))
+            .filter(|(_, names)| !names.is_empty());
+        let mut prescan_string = match &block_patterns {
+            Some((_, _, item_string)) => format!("(?:{line_string})|(?:{item_string})"),
+            None => line_string.clone(),
+        };
+        if let Some((_, names)) = &split_patterns {
+            for (_, name_string) in names {
+                prescan_string.push_str(&format!("|(?m:{name_string})"));
+            }
+        }
+        Some(QueryRegexes {
+            line: Regex::new(&line_string).expect("Could not create regex for file type query"),
+            kinds,
+            block: block_patterns.map(|(starts, end_string, item_string)| BlockRegexes {
+                starts: starts
+                    .into_iter()
+                    .map(|(kind, start_string)| {
+                        let start = Regex::new(&start_string)
+                            .expect("Could not create regex for block start");
+                        (kind, start)
+                    })
+                    .collect(),
+                end: Regex::new(&end_string).expect("Could not create regex for block end"),
+                item: Regex::new(&item_string).expect("Could not create regex for block query"),
+            }),
+            split: split_patterns.map(|(return_type_string, names)| SplitRegexes {
+                return_type: Regex::new(&return_type_string)
+                    .expect("Could not create regex for return type"),
+                names: names
+                    .into_iter()
+                    .map(|(kind, name_string)| {
+                        let name = Regex::new(&name_string)
+                            .expect("Could not create regex for split definition");
+                        (kind, name)
+                    })
+                    .collect(),
+            }),
+            statement: get_statement_pattern(file_type).map(|statement_string| {
+                Regex::new(&statement_string).expect("Could not create regex for statement")
+            }),
+            prescan: Regex::new(&prescan_string).expect("Could not create regex for prescan"),
+            symbol: Regex::new(&format!(r"\b{query}\b"))
+                .expect("Could not create regex for symbol"),
+        })
+    }
+
+    /// Return the kind and the part of the line that is a definition, if any
+    ///
+    /// Lines must be passed in order because [LineState] tracks whether the current line is
+    /// inside a block recognized by [BlockRegexes] and whether it follows a return type.
+    pub fn find_definition<'a>(
+        &self,
+        line: &'a str,
+        state: &mut LineState,
+    ) -> Option<(DefinitionKind, Match<'a>)> {
+        let is_statement = self
+            .statement
+            .as_ref()
+            .is_some_and(|statement| statement.is_match(line));
+        let after_return_type = std::mem::replace(
+            &mut state.after_return_type,
+            !is_statement
+                && self
+                    .split
+                    .as_ref()
+                    .is_some_and(|split| split.return_type.is_match(line)),
+        );
+        if let Some(block) = &self.block {
+            if let Some(kind) = &state.block_kind {
+                let depth = state.block_depth;
+                state.block_depth = depth.saturating_add_signed(get_nesting_change(line));
+                if depth == 0 {
+                    if block.end.is_match(line) {
+                        state.block_kind = None;
+                        state.block_depth = 0;
+                        return None;
+                    }
+                    if let Some(definition) = block.item.find(line) {
+                        return Some((kind.clone(), definition));
+                    }
+                }
+            } else if let Some((kind, _)) = block.starts.iter().find(|(_, re)| re.is_match(line)) {
+                state.block_kind = Some(kind.clone());
+            }
+        }
+        if is_statement {
+            return None;
+        }
+        if let Some(split) = self.split.as_ref().filter(|_| after_return_type) {
+            if let Some((kind, definition)) = split
+                .names
+                .iter()
+                .find_map(|(kind, re)| re.find(line).map(|definition| (kind, definition)))
+            {
+                return Some((kind.clone(), definition));
+            }
+        }
+        let captures = self.line.captures(line)?;
+        self.kinds.iter().enumerate().find_map(|(index, kind)| {
+            captures
+                .name(&format!("d{index}"))
+                .map(|definition| (kind.clone(), definition))
+        })
+    }
+
+    /// Return the position in the line and the symbol name within a definition returned by
+    /// [QueryRegexes::find_definition]
+    pub fn find_symbol<'a>(&self, definition: &Match<'a>) -> Option<(usize, &'a str)> {
+        self.symbol
+            .find(definition.as_str())
+            .map(|symbol| (definition.start() + symbol.start(), symbol.as_str()))
+    }
+}
+
+/// Return how many more brackets are open at the end of the line than at its start
+///
+/// Brackets inside quotes or after a `//` comment are not counted.
+fn get_nesting_change(line: &str) -> isize {
+    let mut change = 0;
+    let mut quote = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(open), _) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'' | '`') => quote = Some(c),
+            (None, '/') if chars.as_str().starts_with('/') => break,
+            (None, '(' | '[' | '{') => change += 1,
+            (None, ')' | ']' | '}') => change -= 1,
+            _ => {}
+        }
+    }
+    change
+}
+
+/// Return the query as it should be put into each regex
+///
+/// Only the query ignores case, not the keywords around it.
+fn get_query_pattern(config: &Config) -> String {
+    if config.ignore_case {
+        return format!("(?i:{})", config.query);
+    }
+    config.query.clone()
+}
+
+/// Remove any patterns for kinds that were not requested by [Config]
+fn filter_patterns_by_kind(config: &Config, patterns: DefinitionPatterns) -> DefinitionPatterns {
+    match &config.kinds {
+        Some(kinds) => patterns
+            .into_iter()
+            .filter(|(kind, _)| kinds.contains(kind))
+            .collect(),
+        None => patterns,
+    }
+}
+
+/// Return true if blocks that add to an existing type (like `impl` blocks) should be found
+fn should_include_impl(config: &Config) -> bool {
+    config.include_impl
+        || config
+            .kinds
+            .as_ref()
+            .is_some_and(|kinds| kinds.contains(&DefinitionKind::Impl))
+}
+
+/// Return the regex strings that find each [DefinitionKind] of the query in a [FileType]
+///
+/// When more than one pattern matches at the same place in a line, the earlier one in the list
+/// determines the [DefinitionKind].
+fn get_definition_patterns_for_query(config: &Config, file_type: &FileType) -> DefinitionPatterns {
+    let query = &get_query_pattern(config);
+    match file_type {
+        FileType::JS => vec![
+            (DefinitionKind::Function, format!(r"\bfunction\s+{query}\b")),
+            (DefinitionKind::Class, format!(r"\bclass\s+{query}\b")),
+            (
+                DefinitionKind::Interface,
+                format!(r"\binterface\s+{query}\b"),
+            ),
+            (DefinitionKind::Type, format!(r"\btype\s+{query}\b")),
+            (
+                DefinitionKind::Type,
+                format!(r"@typedef\s*(\{{[^\}}]+\}})?\s*{query}\b"),
+            ),
+            (
+                DefinitionKind::Variable,
+                format!(r"\b(var|let)\s+{query}\b"),
+            ),
+            (DefinitionKind::Const, format!(r"\bconst\s+{query}\b")),
+            (
+                DefinitionKind::Method,
+                format!(r"\b{query}\([^)]*\)\s*(:[^\{{]+)?\{{"),
+            ),
+            (
+                DefinitionKind::Method,
+                format!(r"\b{query}:\s*(function\b|\([^)]*\)\s*(:[^=]+)?=>)"),
+            ),
+            (DefinitionKind::Property, format!(r"\b{query}:")),
+        ],
+        // Properties need a visibility (or similar) keyword so that they are not confused with
+        // variables, and enum cases need `=` or `;` so they are not confused with switch cases.
+        FileType::PHP => vec![
+            (
+                DefinitionKind::Method,
+                format!(
+                    r"\b(public|protected|private|static|abstract|final)\s+[\w\s]*\bfunction\s+&?\s*{query}\b"
+                ),
+            ),
+            (
+                DefinitionKind::Function,
+                format!(r"\bfunction\s+&?\s*{query}\b"),
+            ),
+            (DefinitionKind::Class, format!(r"\bclass\s+{query}\b")),
+            (DefinitionKind::Trait, format!(r"\btrait\s+{query}\b")),
+            (
+                DefinitionKind::Interface,
+                format!(r"\binterface\s+{query}\b"),
+            ),
+            (DefinitionKind::Enum, format!(r"\benum\s+{query}\b")),
+            (
+                DefinitionKind::Const,
+                format!(r"\bconst\s+(\??\w+\s+)?{query}\s*="),
+            ),
+            (
+                DefinitionKind::Const,
+                format!(r"\bdefine\(\s*['\x22]{query}['\x22]"),
+            ),
+            (
+                DefinitionKind::Property,
+                format!(r"\b(public|protected|private|var|readonly)\b[^$;=(]*\${query}\b"),
+            ),
+            // A bare `static $name` is a variable inside a function, so a static property needs a
+            // type (or one of the keywords above).
+            (
+                DefinitionKind::Property,
+                format!(r"\bstatic\s+\??[\w\\]+(\s*[|&]\s*\??[\w\\]+)*\s+\${query}\b"),
+            ),
+            (DefinitionKind::Const, format!(r"\bcase\s+{query}\s*(=|;)")),
+        ],
+        FileType::RS => {
+            let mut patterns = vec![
+                (DefinitionKind::Function, format!(r"\bfn\s+{query}\b")),
+                (DefinitionKind::Trait, format!(r"\btrait\s+{query}\b")),
+                (DefinitionKind::Enum, format!(r"\benum\s+{query}\b")),
+                (
+                    DefinitionKind::Struct,
+                    format!(r"\b(struct|union)\s+{query}\b"),
+                ),
+                (DefinitionKind::Module, format!(r"\bmod\s+{query}\b")),
+                (DefinitionKind::Const, format!(r"\bconst\s+{query}\b")),
+                (
+                    DefinitionKind::Variable,
+                    format!(r"\bstatic\s+(mut\s+)?{query}\b"),
+                ),
+                (DefinitionKind::Type, format!(r"\btype\s+{query}\b")),
+                (
+                    DefinitionKind::Macro,
+                    format!(r"\bmacro_rules!\s*{query}\b"),
+                ),
+            ];
+            if should_include_impl(config) {
+                // Either `impl Name` or `impl Trait for Name`, with optional generics and paths.
+                patterns.push((
+                    DefinitionKind::Impl,
+                    format!(r"\bimpl\b(<.*>)?\s+([\w:]+::)?{query}\b\s*(<.*>)?\s*(\{{|where\b|$)"),
+                ));
+                patterns.push((
+                    DefinitionKind::Impl,
+                    format!(r"\bimpl\b.*\bfor\s+([\w:]+::)?{query}\b"),
+                ));
+            }
+            patterns
+        }
+        // Indented functions are assumed to be methods, and assignments only count as
+        // definitions at the module level (without indentation).
+        FileType::PY => vec![
+            (
+                DefinitionKind::Method,
+                format!(r"^\s+(async\s+)?def\s+{query}\b"),
+            ),
+            (DefinitionKind::Function, format!(r"\bdef\s+{query}\b")),
+            (DefinitionKind::Class, format!(r"\bclass\s+{query}\b")),
+            (
+                DefinitionKind::Type,
+                format!(r"^{query}\s*:\s*(typing\.)?TypeAlias\b"),
+            ),
+            (
+                DefinitionKind::Type,
+                format!(r"\btype\s+{query}\s*(\[[^\]]*\])?\s*="),
+            ),
+            (
+                DefinitionKind::Variable,
+                format!(r"^{query}\s*(:[^=]+)?=($|[^=])"),
+            ),
+        ],
+        FileType::GO => vec![
+            (
+                DefinitionKind::Method,
+                format!(r"\bfunc\s+\([^)]*\)\s*{query}\s*[\[(]"),
+            ),
+            (
+                DefinitionKind::Function,
+                format!(r"\bfunc\s+{query}\s*[\[(]"),
+            ),
+            (
+                DefinitionKind::Struct,
+                format!(r"\btype\s+{query}\s+struct\b"),
+            ),
+            (
+                DefinitionKind::Interface,
+                format!(r"\btype\s+{query}\s+interface\b"),
+            ),
+            (DefinitionKind::Type, format!(r"\btype\s+{query}\b")),
+            (DefinitionKind::Const, format!(r"\bconst\s+{query}\b")),
+            (DefinitionKind::Variable, format!(r"\bvar\s+{query}\b")),
+        ],
+        // A method needs modifiers or a return type that looks like a type (a primitive or a
+        // capitalized name) so that calls like `return query(...)` are not matched.
+        FileType::JAVA => vec![
+            (
+                DefinitionKind::Class,
+                format!(r"\b(class|record)\s+{query}\b"),
+            ),
+            (
+                DefinitionKind::Interface,
+                format!(r"\binterface\s+{query}\b"),
+            ),
+            (DefinitionKind::Enum, format!(r"\benum\s+{query}\b")),
+            (
+                DefinitionKind::Method,
+                format!(
+                    r"^\s*(@\w+(\([^)]*\))?\s+)*((public|protected|private|static|final|abstract|synchronized|native|default|strictfp)\s+)*(<[^()]*>\s+)?(void|boolean|byte|char|short|int|long|float|double|([\w$]+\.)*[A-Z][\w$]*)\s*(<[^()]*>)?\s*(\[\]\s*)*\s+{query}\s*\("
+                ),
+            ),
+            (
+                DefinitionKind::Method,
+                format!(r"^\s*(@\w+(\([^)]*\))?\s+)*(public|protected|private)\s+{query}\s*\("),
+            ),
+        ],
+        // Like Python, indented functions are assumed to be methods and variables only count as
+        // definitions at the top level (without indentation).
+        FileType::KT => vec![
+            (
+                DefinitionKind::Method,
+                format!(r"^\s+(\w+\s+)*fun\s+(<[^>]*>\s*)?([\w.<>?]+\.)?{query}\s*\("),
+            ),
+            (
+                DefinitionKind::Function,
+                format!(r"\bfun\s+(<[^>]*>\s*)?([\w.<>?]+\.)?{query}\s*\("),
+            ),
+            (DefinitionKind::Enum, format!(r"\benum\s+class\s+{query}\b")),
+            (
+                DefinitionKind::Interface,
+                format!(r"\binterface\s+{query}\b"),
+            ),
+            (
+                DefinitionKind::Class,
+                format!(r"\b(class|object)\s+{query}\b"),
+            ),
+            (DefinitionKind::Type, format!(r"\btypealias\s+{query}\b")),
+            (
+                DefinitionKind::Const,
+                format!(r"^(\w+\s+)*const\s+val\s+{query}\b"),
+            ),
+            (
+                DefinitionKind::Variable,
+                format!(r"^(\w+\s+)*(val|var)\s+{query}\b"),
+            ),
+        ],
+        // Like Python, indented methods are assumed to be part of a class or module.
+        FileType::RB => {
+            let mut patterns = vec![
+                (
+                    DefinitionKind::Method,
+                    format!(r"^\s+(\w+\s+)*def\s+(self\.)?{query}\b"),
+                ),
+                (DefinitionKind::Method, format!(r"\bdef\s+self\.{query}\b")),
+                (DefinitionKind::Function, format!(r"\bdef\s+{query}\b")),
+                (
+                    DefinitionKind::Class,
+                    format!(r"\bclass\s+(\w+::)*{query}\b"),
+                ),
+                (
+                    DefinitionKind::Module,
+                    format!(r"\bmodule\s+(\w+::)*{query}\b"),
+                ),
+                (
+                    DefinitionKind::Property,
+                    format!(r"\battr_(accessor|reader|writer)\b\s*\(?\s*(:\w+\s*,\s*)*:{query}\b"),
+                ),
+                (
+                    DefinitionKind::Method,
+                    format!(r#"\b(define_method|alias_method)\b\s*\(?\s*[:"']{query}\b"#),
+                ),
+            ];
+            // Only names that start with a capital letter are constants; others are variables.
+            if config.ignore_case || config.query.starts_with(|c: char| c.is_ascii_uppercase()) {
+                patterns.push((
+                    DefinitionKind::Const,
+                    format!(r"^\s*(\w+::)*{query}\s*=($|[^=~>])"),
+                ));
+            }
+            patterns
+        }
+        // Like Java, methods and properties need a return type that is a primitive or a
+        // capitalized name so that calls like `return query(...)` are not matched.
+        FileType::CS => {
+            let prefix = r"^\s*(\[[^\]]*\]\s*)*((public|protected|private|internal|static|virtual|override|abstract|sealed|async|extern|unsafe|new|partial|readonly|required)\s+)*";
+            let return_type = r"(void|bool|byte|sbyte|char|decimal|double|float|int|uint|long|ulong|short|ushort|nint|nuint|object|string|dynamic|(\w+\.)*[A-Z]\w*)\s*(<[^()]*>)?\??(\[[,\s]*\])*";
+            vec![
+                (
+                    DefinitionKind::Module,
+                    format!(r"^\s*namespace\s+(\w+\.)*{query}\b"),
+                ),
+                (
+                    DefinitionKind::Class,
+                    format!(r"\b(class|record)\s+{query}\b"),
+                ),
+                (DefinitionKind::Struct, format!(r"\bstruct\s+{query}\b")),
+                (
+                    DefinitionKind::Interface,
+                    format!(r"\binterface\s+{query}\b"),
+                ),
+                (DefinitionKind::Enum, format!(r"\benum\s+{query}\b")),
+                (
+                    DefinitionKind::Type,
+                    format!(r"\bdelegate\s+{return_type}\s+{query}\s*[<(]"),
+                ),
+                (
+                    DefinitionKind::Method,
+                    format!(r"{prefix}{return_type}\s+{query}\s*(<[^()]*>)?\s*\("),
+                ),
+                (
+                    DefinitionKind::Method,
+                    format!(
+                        r"^\s*(\[[^\]]*\]\s*)*((public|protected|private|internal|static)\s+)+{query}\s*\("
+                    ),
+                ),
+                (
+                    DefinitionKind::Property,
+                    format!(r"{prefix}{return_type}\s+{query}\s*(\{{|=>|$)"),
+                ),
+            ]
+        }
+        // Like Python, indented functions are assumed to be methods.
+        FileType::SWIFT => {
+            let mut patterns = vec![
+                (
+                    DefinitionKind::Method,
+                    format!(r"^\s+(@\w+(\([^)]*\))?\s+)*(\w+\s+)*func\s+{query}\b"),
+                ),
+                (DefinitionKind::Function, format!(r"\bfunc\s+{query}\b")),
+                (
+                    DefinitionKind::Class,
+                    format!(r"\b(class|actor)\s+{query}\b"),
+                ),
+                (DefinitionKind::Struct, format!(r"\bstruct\s+{query}\b")),
+                (DefinitionKind::Enum, format!(r"\benum\s+{query}\b")),
+                (
+                    DefinitionKind::Interface,
+                    format!(r"\bprotocol\s+{query}\b"),
+                ),
+                (
+                    DefinitionKind::Type,
+                    format!(r"\b(typealias|associatedtype)\s+{query}\b"),
+                ),
+                (
+                    DefinitionKind::Variable,
+                    format!(r"\b(let|var)\s+{query}\b"),
+                ),
+            ];
+            if should_include_impl(config) {
+                patterns.push((
+                    DefinitionKind::Impl,
+                    format!(r"\bextension\s+([\w.]+\.)?{query}\b"),
+                ));
+            }
+            patterns
+        }
+        // Like Java, functions need a return type that is a primitive or a capitalized name so
+        // that calls like `return query(...)` are not matched. Indented functions are assumed to
+        // be methods.
+        FileType::DART => {
+            let return_type =
+                r"(void|int|double|num|bool|dynamic|([\w$]+\.)*[A-Z][\w$]*)\s*(<[^()]*>)?\??";
+            let mut patterns = vec![
+                (
+                    DefinitionKind::Method,
+                    format!(
+                        r"^\s+((static|external|abstract)\s+)*{return_type}\s+{query}\s*(<[^()]*>)?\s*\("
+                    ),
+                ),
+                (
+                    DefinitionKind::Function,
+                    format!(r"^(external\s+)?{return_type}\s+{query}\s*(<[^()]*>)?\s*\("),
+                ),
+                (DefinitionKind::Trait, format!(r"\bmixin\s+{query}\b")),
+                (DefinitionKind::Class, format!(r"\bclass\s+{query}\b")),
+                (DefinitionKind::Enum, format!(r"\benum\s+{query}\b")),
+                (
+                    DefinitionKind::Type,
+                    format!(r"\btypedef\s+({return_type}\s+)?{query}\b"),
+                ),
+                // A named extension is the only definition of its name.
+                (DefinitionKind::Impl, format!(r"\bextension\s+{query}\b")),
+            ];
+            if should_include_impl(config) {
+                // An extension (named or not) that adds to the type with `on Name`.
+                patterns.push((
+                    DefinitionKind::Impl,
+                    format!(r"\bextension\b.*\bon\s+{query}\b"),
+                ));
+            }
+            patterns
+        }
+        // Like Python, indented functions are assumed to be methods. Values are usually members
+        // of an `object` so they are found at any indentation.
+        FileType::SCALA => vec![
+            (
+                DefinitionKind::Method,
+                format!(r"^\s+(\w+(\[\w*\])?\s+)*def\s+{query}\b"),
+            ),
+            (DefinitionKind::Function, format!(r"\bdef\s+{query}\b")),
+            (
+                DefinitionKind::Class,
+                format!(r"\b(class|object)\s+{query}\b"),
+            ),
+            (DefinitionKind::Trait, format!(r"\btrait\s+{query}\b")),
+            (DefinitionKind::Enum, format!(r"\benum\s+{query}\b")),
+            (DefinitionKind::Type, format!(r"\btype\s+{query}\b")),
+            (
+                DefinitionKind::Variable,
+                format!(r"\b(val|var)\s+{query}\b"),
+            ),
+        ],
+        // A function definition is a name with a return type before it and a parameter list after
+        // it on a line that does not end in `;`, so that prototypes and most calls are not
+        // matched. Indented and qualified (`Class::name`) functions are assumed to be methods.
+        FileType::C => vec![
+            (
+                DefinitionKind::Macro,
+                format!(r"^\s*#\s*define\s+{query}\b"),
+            ),
+            (
+                DefinitionKind::Method,
+                format!(r"^([\w:<>,]+[\s*&]+)*([\w<>]+::)+~?{query}\s*\((.*[^;\s])?\s*$"),
+            ),
+            (
+                DefinitionKind::Function,
+                format!(r"^([\w:<>,]+[\s*&]+)+{query}\s*\((.*[^;\s])?\s*$"),
+            ),
+            (
+                DefinitionKind::Method,
+                format!(r"^\s+([\w:<>,]+[\s*&]+)+~?{query}\s*\((.*[^;\s])?\s*$"),
+            ),
+            (
+                DefinitionKind::Module,
+                format!(r"^\s*(inline\s+)?namespace\s+(\w+::)*{query}\b"),
+            ),
+            (
+                DefinitionKind::Enum,
+                format!(r"\benum\s+((class|struct)\s+)?{query}\s*(\{{|:[^:]|$)"),
+            ),
+            (
+                DefinitionKind::Struct,
+                format!(r"\b(struct|union)\s+{query}\s*(\{{|:[^:]|final\b|$)"),
+            ),
+            (
+                DefinitionKind::Class,
+                format!(r"\bclass\s+{query}\s*(\{{|:[^:]|final\b|$)"),
+            ),
+            (
+                DefinitionKind::Type,
+                format!(r"\btypedef\b.*\b{query}\s*(;|\[|\)\s*\()"),
+            ),
+            // The name at the end of a multi-line `typedef struct { ... } Name;`
+            (DefinitionKind::Type, format!(r"^\}}\s*{query}\s*;")),
+            (DefinitionKind::Type, format!(r"\busing\s+{query}\s*=")),
+        ],
+    }
+}
+
+/// Return the return type and name regex strings for a [SplitRegexes] if the file type has one
+fn get_split_patterns_for_query(
+    query: &str,
+    file_type: &FileType,
+) -> Option<(String, DefinitionPatterns)> {
+    match file_type {
+        // GNU style puts the return type on its own line and the name at the start of the next.
+        FileType::C => Some((
+            String::from(r"^[A-Za-z_][\w:<>,\s*&]*$"),
+            vec![
+                (
+                    DefinitionKind::Method,
+                    format!(r"^([\w<>]+::)+~?{query}\s*\((.*[^;\s])?\s*$"),
+                ),
+                (
+                    DefinitionKind::Function,
+                    format!(r"^{query}\s*\((.*[^;\s])?\s*$"),
+                ),
+            ],
+        )),
+        _ => None,
+    }
+}
+
+/// Return the regex string for a line that cannot be a definition, if the file type has one
+fn get_statement_pattern(file_type: &FileType) -> Option<String> {
+    match file_type {
+        // Keywords like `return` would otherwise be taken for the return type of a function, and
+        // labels like `public:` for a return type on the line before a function's name.
+        FileType::C => Some(String::from(
+            r"^\s*((return|else|case|throw|delete|new|goto|sizeof|co_return|co_yield|co_await)\b|(public|protected|private|default)\s*:\s*$)",
+        )),
+        _ => None,
+    }
+}
+
+/// Return the start, end, and item regex strings for a [BlockRegexes] if the file type has one
+fn get_block_patterns_for_query(
+    query: &str,
+    file_type: &FileType,
+) -> Option<(DefinitionPatterns, String, String)> {
+    match file_type {
+        // Grouped declarations like `const ( ... )` list one name per line without a keyword.
+        FileType::GO => Some((
+            vec![
+                (
+                    DefinitionKind::Const,
+                    String::from(r"^\s*const\s*\(\s*(//.*)?$"),
+                ),
+                (
+                    DefinitionKind::Variable,
+                    String::from(r"^\s*var\s*\(\s*(//.*)?$"),
+                ),
+                (
+                    DefinitionKind::Type,
+                    String::from(r"^\s*type\s*\(\s*(//.*)?$"),
+                ),
+            ],
+            String::from(r"^\s*\)"),
+            format!(r"(?m)^\s*{query}\b\s*($|[,=\w*\[])"),
+        )),
+        _ => None,
+    }
+}
@@ -1,4 +1,5 @@
 use std::num::NonZero;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -13,7 +14,7 @@ struct Worker {
 }
 
 impl Worker {
-    pub fn new(id: usize, receiver: WorkerReceiver) -> Worker {
+    pub fn new(id: usize, receiver: WorkerReceiver, cancelled: Arc<AtomicBool>) -> Worker {
         let thread = thread::spawn(move || loop {
             // recv will block until the next job is sent.
             let message = receiver
@@ -23,7 +24,11 @@ impl Worker {
 
             match message {
                 Ok(job) => {
-                    job();
+                    // Jobs still in the channel after the pool is cancelled are dropped without
+                    // running.
+                    if !cancelled.load(Ordering::Relaxed) {
+                        job();
+                    }
                 }
                 // The thread will stop when the job channel is sent an Err, which will happen when
                 // the channel is closed.
@@ -42,6 +47,7 @@ impl Worker {
 pub struct ThreadPool {
     workers: Vec<Worker>,
     sender: Option<mpsc::Sender<Job>>,
+    cancelled: Arc<AtomicBool>,
 }
 
 impl ThreadPool {
@@ -50,16 +56,22 @@ impl ThreadPool {
         let (sender, receiver) = mpsc::channel();
 
         let receiver = Arc::new(Mutex::new(receiver));
+        let cancelled = Arc::new(AtomicBool::new(false));
 
         let mut workers = Vec::with_capacity(count.into());
 
         for id in 0..count.into() {
-            workers.push(Worker::new(id, Arc::clone(&receiver)));
+            workers.push(Worker::new(
+                id,
+                Arc::clone(&receiver),
+                Arc::clone(&cancelled),
+            ));
         }
 
         ThreadPool {
             workers,
             sender: Some(sender),
+            cancelled,
         }
     }
 
@@ -75,6 +87,11 @@ impl ThreadPool {
             .expect("Unable to send data to search thread");
     }
 
+    pub fn cancel(&self) {
+        // Jobs that are already running will finish but no new ones will start.
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
     pub fn wait_for_all_jobs_and_stop(&mut self) {
         // Close the Jobs channel which will trigger each thread to stop when it finishes its
         // current work.
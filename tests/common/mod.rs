@@ -1,4 +1,5 @@
 use grepdef::{Args, SearchResult, Searcher};
+use std::path::PathBuf;
 
 pub fn make_args(
     query: String,
@@ -7,15 +8,25 @@ pub fn make_args(
 ) -> Args {
     Args {
         query,
-        file_path: match file_path {
-            Some(file_path) => Some(file_path.split_whitespace().map(String::from).collect()),
-            None => None,
-        },
-        file_type: file_type_string,
+        file_path: file_path
+            .map(|file_path| file_path.split_whitespace().map(String::from).collect()),
+        file_type: file_type_string.map(|file_type_string| vec![file_type_string]),
+        ignore_case: false,
+        smart_case: false,
         line_number: true,
+        column: false,
+        kinds: None,
+        show_kind: false,
+        include_impl: false,
         search_method: None,
+        sort: None,
+        max_results: None,
+        stdin_filename: None,
+        no_messages: false,
         debug: false,
         no_color: false,
+        format: None,
+        vimgrep: false,
         threads: None,
     }
 }
@@ -25,6 +36,13 @@ pub fn do_search(args: Args) -> Vec<SearchResult> {
     searcher.search().expect("Search failed for test")
 }
 
+// Create an empty directory for a test that needs files which can't be committed as fixtures.
+pub fn make_temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("grepdef-{}-{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
 pub fn get_default_fixture_for_file_type_string(file_type_string: &str) -> Result<String, String> {
     match file_type_string {
         "js" => Ok(String::from("./tests/fixtures/by-language/js-fixture.js")),
@@ -33,12 +51,28 @@ pub fn get_default_fixture_for_file_type_string(file_type_string: &str) -> Resul
         "tsx" => Ok(String::from("./tests/fixtures/by-language/tsx-fixture.tsx")),
         "php" => Ok(String::from("./tests/fixtures/by-language/php-fixture.php")),
         "rs" => Ok(String::from("./tests/fixtures/by-language/rs-fixture.rs")),
-        _ => {
-            return Err(format!(
-                "No fixture found for file type '{}'",
-                file_type_string
-            ))
-        }
+        "py" => Ok(String::from("./tests/fixtures/by-language/py-fixture.py")),
+        "go" => Ok(String::from("./tests/fixtures/by-language/go-fixture.go")),
+        "java" => Ok(String::from(
+            "./tests/fixtures/by-language/java-fixture.java",
+        )),
+        "kt" => Ok(String::from("./tests/fixtures/by-language/kt-fixture.kt")),
+        "c" => Ok(String::from("./tests/fixtures/by-language/cpp-fixture.cpp")),
+        "rb" => Ok(String::from("./tests/fixtures/by-language/rb-fixture.rb")),
+        "cs" => Ok(String::from("./tests/fixtures/by-language/cs-fixture.cs")),
+        "swift" => Ok(String::from(
+            "./tests/fixtures/by-language/swift-fixture.swift",
+        )),
+        "dart" => Ok(String::from(
+            "./tests/fixtures/by-language/dart-fixture.dart",
+        )),
+        "scala" => Ok(String::from(
+            "./tests/fixtures/by-language/scala-fixture.scala",
+        )),
+        _ => Err(format!(
+            "No fixture found for file type '{}'",
+            file_type_string
+        )),
     }
 }
 
@@ -52,20 +86,67 @@ pub fn get_expected_text_line_for_test_search(
         "tsx" => Ok((String::from("function parseQueryTS(): string {"), 7)),
         "php" => Ok((String::from("function parseQuery() {"), 6)),
         "rs" => Ok((String::from("pub fn query_db() -> bool {}"), 1)),
-        _ => {
-            return Err(format!(
-                "No expected text found for file type '{}'",
-                file_type_string
-            ))
-        }
+        "py" => Ok((String::from("def query_db_py():"), 1)),
+        "go" => Ok((String::from("func queryDbGo() bool {}"), 3)),
+        "java" => Ok((
+            String::from(
+                "public static List<String> parseQueryJava(String query) throws Exception {",
+            ),
+            12,
+        )),
+        "kt" => Ok((String::from("fun queryDbKt(): Boolean = true"), 9)),
+        "c" => Ok((
+            String::from("static int count_queries_cpp(int a, int b) {"),
+            35,
+        )),
+        "rb" => Ok((String::from("def query_db_rb"), 6)),
+        "cs" => Ok((
+            String::from("public static List<string> ParseQueryCs(string text)"),
+            42,
+        )),
+        "swift" => Ok((String::from("func queryDbSwift() -> Bool {"), 8)),
+        "dart" => Ok((String::from("bool queryDbDart() => true;"), 6)),
+        "scala" => Ok((String::from("def queryDbScala(): Boolean = true"), 3)),
+        _ => Err(format!(
+            "No expected text found for file type '{}'",
+            file_type_string
+        )),
+    }
+}
+
+pub fn get_expected_symbol_for_test_search(file_type_string: &str) -> Result<String, String> {
+    match file_type_string {
+        "js" | "jsx" | "php" => Ok(String::from("parseQuery")),
+        "ts" | "tsx" => Ok(String::from("parseQueryTS")),
+        "rs" => Ok(String::from("query_db")),
+        "py" => Ok(String::from("query_db_py")),
+        "go" => Ok(String::from("queryDbGo")),
+        "java" => Ok(String::from("parseQueryJava")),
+        "kt" => Ok(String::from("queryDbKt")),
+        "c" => Ok(String::from("count_queries_cpp")),
+        "rb" => Ok(String::from("query_db_rb")),
+        "cs" => Ok(String::from("ParseQueryCs")),
+        "swift" => Ok(String::from("queryDbSwift")),
+        "dart" => Ok(String::from("queryDbDart")),
+        "scala" => Ok(String::from("queryDbScala")),
+        _ => Err(format!(
+            "No expected symbol found for file type '{}'",
+            file_type_string
+        )),
     }
 }
 
 pub fn get_expected_search_result_for_file_type(file_type_string: &str) -> SearchResult {
     let (text, line_number) = get_expected_text_line_for_test_search(file_type_string).unwrap();
     SearchResult {
-        file_path: get_default_fixture_for_file_type_string(file_type_string).unwrap(),
+        file_path: get_default_fixture_for_file_type_string(file_type_string)
+            .unwrap()
+            .into(),
         line_number: Some(line_number),
+        column: None,
+        byte_offset: None,
+        symbol: get_expected_symbol_for_test_search(file_type_string).unwrap(),
+        kind: None,
         text,
     }
 }
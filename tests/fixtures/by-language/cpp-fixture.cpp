@@ -0,0 +1,94 @@
+#include <string>
+
+#define QUERY_MAX_CPP 10
+#define query_square_cpp(x) ((x) * (x))
+
+typedef int (*query_callback_cpp)(int);
+typedef unsigned long QueryIdCpp;
+
+using QueryListCpp = std::vector<std::string>;
+
+namespace query_space_cpp {
+
+struct QueryNodeCpp {
+    int value;
+};
+
+union QueryValueCpp {
+    int number;
+    float real;
+};
+
+typedef struct {
+    int count;
+} QueryStateCpp;
+
+enum QueryColorCpp { Red, Green };
+
+enum class QueryKindCpp : int {
+    First,
+    Second,
+};
+
+int parse_query_cpp(const char *query);
+
+static int count_queries_cpp(int a, int b) {
+    return parse_query_cpp("x") + a + b;
+}
+
+const char *
+format_query_cpp (const char *query,
+                  int flags)
+{
+    int result = count_queries_cpp(1, 2);
+    query_helper_cpp(result);
+    return query;
+}
+
+template <typename T>
+T max_query_cpp(T a, T b) {
+    return a > b ? a : b;
+}
+
+class QueryParserCpp : public QueryBase {
+public:
+    QueryParserCpp(int value);
+    std::string run_query_cpp(int value) const {
+        return std::to_string(value);
+    }
+    virtual ~QueryParserCpp();
+};
+
+QueryParserCpp::QueryParserCpp(int value) {
+}
+
+bool QueryParserCpp::check_query_cpp(int value) {
+    return value > 0;
+}
+
+void
+QueryParserCpp::reset_query_cpp()
+{
+}
+
+} // namespace query_space_cpp
+
+class QueryRunnerCpp {
+public:
+DECLARE_QUERY_CPP(QueryRunnerCpp)
+    int next_query_cpp(int value) {
+        if (value > 0)
+            return compute_query_cpp(value,
+                                     1);
+        else
+            throw make_query_error_cpp(value,
+                                       2);
+        switch (value) {
+        case 1:
+            return co_query_cpp(1,
+                                2);
+        }
+        delete release_query_cpp(value,
+                                 3);
+    }
+};
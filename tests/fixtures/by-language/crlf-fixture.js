@@ -0,0 +1,4 @@
+const firstCrlfQuery = 1;
+
+function secondCrlfQuery() {
+}
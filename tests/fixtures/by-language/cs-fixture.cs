@@ -0,0 +1,58 @@
+using System;
+
+namespace Queries.QueryToolsCs;
+
+public delegate bool QueryFilterCs<T>(T value);
+
+public interface IQuerierCs
+{
+    Task<int> RunQueryCs(string query);
+}
+
+public enum QueryKindCs
+{
+    First,
+    Second,
+}
+
+public struct QueryPointCs
+{
+    public int X;
+}
+
+public record QueryRecordCs(string Name);
+
+[Serializable]
+public sealed class QueryParserCs : IQuerierCs
+{
+    public QueryParserCs(int size)
+    {
+        QuerySizeCs = size;
+    }
+
+    public int QuerySizeCs { get; }
+
+    public string QueryNameCs => "query";
+
+    public List<string>? QueryListCs
+    {
+        get { return null; }
+    }
+
+    public static List<string> ParseQueryCs(string text)
+    {
+        var result = ParseQueryHelperCs(text);
+        return SplitQueryCs(result);
+    }
+
+    [Obsolete]
+    private async Task<int> RunQueryCs(string query)
+    {
+        int localQueryCs = 0;
+        return await FetchQueryCs<int>(query);
+    }
+
+    internal T FetchQueryCs<T>(string query) => default;
+
+    void ResetQueryCs() { }
+}
@@ -0,0 +1,6 @@
+namespace Queries.QueryBlockCs
+{
+    internal class QueryBlockClassCs
+    {
+    }
+}
@@ -0,0 +1,33 @@
+import 'dart:async';
+
+typedef QueryCallbackDart = void Function(int);
+typedef void LegacyCallbackDart(int value);
+
+bool queryDbDart() => true;
+
+Future<List<String>> fetchQueriesDart<T>(String query) async {
+  return await fetchHelperDart(query);
+}
+
+mixin QueryMixinDart {
+  void mixinQueryDart() {}
+}
+
+enum QueryKindDart { first, second }
+
+abstract class QueryParserDart with QueryMixinDart {
+  QueryParserDart(this.size);
+
+  final int size;
+
+  static String? parseQueryDart(String text) {
+    var result = parseQueryHelperDart(text);
+    return result;
+  }
+
+  int countQueriesDart() => size;
+}
+
+extension QueryExtDart on QueryParserDart {
+  void extraQueryDart() {}
+}
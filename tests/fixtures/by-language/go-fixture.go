@@ -0,0 +1,57 @@
+package main
+
+func queryDbGo() bool {}
+func queryDbGoFake() bool {}
+
+func (p *QueryParserGo) parseQueryGo(query string) error {
+	parseQueryHelperGo = 1
+	return nil
+}
+
+func mapQueriesGo[T any](items []T) []T {
+	return items
+}
+
+type QueryParserGo struct {
+	name string
+}
+
+type QuerierGo interface {
+	Query() error
+}
+
+const MaxQueriesGo = 10
+
+var defaultQueryGo = "default"
+
+const (
+	QueryKindOneGo QueryKindGo = iota
+	QueryKindTwoGo
+)
+
+var (
+	queryCountGo int
+	queryNameGo  = "name"
+)
+
+func runQueriesGo() {
+	queryCountGo = 5
+	queryNameGo = "other"
+	queryDbGo()
+}
+
+type (
+	QueryOptionsGo struct {
+		QueryLimitGo int
+	}
+	QueryIdGo int
+)
+
+const (
+	QueryStartGo    = 1
+	QueryComputedGo = computeQueryGo(
+		2,
+	)
+	QuerySeparatorGo = ")"
+	QueryEndGo       = 3
+)
@@ -0,0 +1,48 @@
+package com.example.queries;
+
+import java.util.List;
+
+public class QueryParserJava {
+    private final String source;
+
+    public QueryParserJava(String source) {
+        this.source = source;
+    }
+
+    public static List<String> parseQueryJava(String query) throws Exception {
+        return splitQueryJava(query);
+    }
+
+    @Override
+    public String toString() {
+        QueryParserJava copy = new QueryParserJava(source);
+        return formatQueryJava(copy.source);
+    }
+
+    private <T> T convertQueryJava(Object query) {
+        return (T) query;
+    }
+
+    int[] countQueriesJava() {
+        return new int[] {};
+    }
+
+    String formatQueryJava(String source) {
+        return source;
+    }
+}
+
+interface QuerierJava {
+    void runQueryJava(String query);
+}
+
+enum QueryKindJava {
+    SELECT,
+    INSERT
+}
+
+record QueryRecordJava(String text) {}
+
+@interface QueryAnnotationJava {
+    String value();
+}
@@ -0,0 +1,49 @@
+package com.example.queries
+
+const val MAX_QUERIES_KT = 10
+val defaultQueryKt = "SELECT 1"
+private var queryCountKt = 0
+
+typealias QueryListKt = List<String>
+
+fun queryDbKt(): Boolean = true
+
+fun <T> convertQueryKt(query: Any): T = query as T
+
+fun String.toQueryKt(): String = this
+
+suspend fun fetchQueryKt(id: Int): String {
+    return parseQueryHelperKt(id)
+}
+
+data class QueryResultKt(val rows: Int)
+
+class QueryParserKt {
+    fun parseQueryKt(query: String): String {
+        val localQueryKt = query.trim()
+        return localQueryKt
+    }
+
+    private fun validateQueryKt(query: String) = query.isNotEmpty()
+
+    companion object QueryFactoryKt {
+        fun create() = QueryParserKt()
+    }
+}
+
+object QueryCacheKt
+
+interface QuerierKt {
+    fun runQueryKt(query: String)
+}
+
+fun interface QueryCallbackKt {
+    fun invoke(query: String)
+}
+
+enum class QueryKindKt {
+    SELECT,
+    INSERT,
+}
+
+sealed class QueryErrorKt : Exception()
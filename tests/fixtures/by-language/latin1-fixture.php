@@ -0,0 +1,7 @@
+<?php
+
+// Cr par un dveloppeur
+function parseLatin1Php() { // rsum
+	return "caf";
+}
+/* caf */ function parseLatin1CommentPhp() {}
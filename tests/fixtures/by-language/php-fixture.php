@@ -26,3 +26,52 @@ class MyClass {
 	public function doSomethingAbsolute(): \Home\Foo {
 	}
 }
+
+const MAX_QUERIES_PHP = 10;
+define('QUERY_PREFIX_PHP', 'q_');
+define( "QUERY_SUFFIX_PHP", '_q' );
+
+abstract class AbstractQueryPhp {
+	const   DEFAULT_LIMIT_PHP = 5;
+	final public const string TYPED_LIMIT_PHP = 'ten';
+	private ?Foo $queryNamePhp;
+	protected static int $queryCountPhp = 0;
+	public readonly string $queryIdPhp;
+	var $legacyQueryPhp;
+
+	public function __construct(private ?Foo $promotedQueryPhp) {
+		$localQueryPhp = $this->queryNamePhp;
+	}
+
+	abstract protected function runQueryPhp(): void;
+
+	public function &getQueryRefPhp(): array {
+	}
+}
+
+final class   FinalQueryPhp {
+}
+
+readonly class ReadonlyQueryPhp {
+}
+
+enum QueryStatusPhp: string {
+	case ActivePhp = 'active';
+	case InactivePhp = 'inactive';
+}
+
+switch ($status) {
+	case SwitchCasePhp:
+		break;
+}
+
+function cacheQueriesPhp() {
+	static $queryCachePhp;
+	static $queryTotalPhp = 0;
+	return $queryCachePhp;
+}
+
+class StaticQueryPhp {
+	static ?array $staticQueryPhp = null;
+	static int|string $staticUnionPhp;
+}
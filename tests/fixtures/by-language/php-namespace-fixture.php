@@ -0,0 +1,5 @@
+<?php
+namespace Queries\Namespaced;
+
+function namespacedQueryPhp(): void {
+}
@@ -0,0 +1,24 @@
+def query_db_py():
+    pass
+
+def query_db_py_fake():
+    pass
+
+async def fetch_query_py():
+    pass
+
+class QueryParserPy:
+    def parse_query_py(self):
+        parse_query_helper_py = 1
+        return parse_query_helper_py
+
+    async def parse_query_async_py(self):
+        pass
+
+MAX_QUERIES_PY = 10
+QueryIdPy: TypeAlias = int
+type QueryListPy = list[QueryIdPy]
+
+result_py = query_db_py()
+if MAX_QUERIES_PY == 10:
+    pass
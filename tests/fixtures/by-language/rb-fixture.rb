@@ -0,0 +1,52 @@
+require "json"
+
+MAX_QUERIES_RB = 10
+query_count_rb = 0
+
+def query_db_rb
+  true
+end
+
+def self.top_query_rb
+end
+
+module QueryHelpersRb
+  DEFAULT_QUERY_RB = "all"
+
+  def helper_query_rb(value)
+    value
+  end
+end
+
+class QueryParserRb < Base
+  include QueryHelpersRb
+
+  attr_accessor :query_text_rb, :query_size_rb
+  attr_reader :query_id_rb
+
+  def initialize
+    @query_text_rb = parse_query_helper_rb
+  end
+
+  def self.build_query_rb
+    new
+  end
+
+  def parse_query_rb(text)
+    local_query_rb = text
+    local_query_rb
+  end
+
+  private def run_query_rb
+  end
+
+  define_method(:dynamic_query_rb) { true }
+  alias_method :old_query_rb, :parse_query_rb
+end
+
+class Admin::QueryAdminRb
+end
+
+if MAX_QUERIES_RB == 10
+  query_db_rb
+end
@@ -31,3 +31,27 @@ where
     F: FnOnce(Vec<SearchResult>) + Send + 'static,
 {
 }
+
+pub(crate) const MAX_SIZE_RS: usize = 10;
+static GLOBAL_RS: &str = "global";
+static mut COUNTER_RS: usize = 0;
+pub type AliasRs = Vec<String>;
+
+union UnionRs {
+    a: u32,
+}
+
+macro_rules! macro_rs {
+    () => {};
+}
+
+pub(crate) async unsafe fn qualified_fn_rs() {}
+const fn const_fn_rs() {}
+extern "C" fn extern_fn_rs() {}
+pub    fn   spaced_fn_rs() {}
+
+struct GenericRs<T> {}
+trait TraitRs {}
+
+impl<T> TraitRs for GenericRs<T> {}
+impl std::fmt::Display for ContainerWithBlock {}
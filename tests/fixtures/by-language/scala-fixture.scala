@@ -0,0 +1,30 @@
+package queries
+
+def queryDbScala(): Boolean = true
+
+trait QuerierScala {
+  def runQueryScala(): Unit
+}
+
+case class QueryResultScala(name: String)
+
+sealed abstract class QueryParserScala extends QuerierScala {
+  private[queries] def parseQueryScala(text: String): List[String] = {
+    val localQueryScala = parseQueryHelperScala(text)
+    localQueryScala
+  }
+
+  override def runQueryScala(): Unit = ()
+}
+
+object QueryParserScala {
+  val maxQueriesScala = 10
+  var queryCountScala = 0
+  type QueryIdScala = Int
+}
+
+case object QueryEmptyScala
+
+enum QueryKindScala {
+  case First
+}
@@ -0,0 +1,40 @@
+import Foundation
+
+let maxQueriesSwift = 10
+var queryCountSwift = 0
+
+typealias QueryIdSwift = Int
+
+func queryDbSwift() -> Bool {
+    return true
+}
+
+protocol QuerierSwift {
+    associatedtype QueryResultSwift
+    func runQuerySwift() -> QueryResultSwift
+}
+
+enum QueryKindSwift {
+    case first
+}
+
+struct QueryPointSwift {
+    var x: Int
+}
+
+class QueryParserSwift: QuerierSwift {
+    private let querySizeSwift = 3
+
+    @discardableResult
+    public static func parseQuerySwift(_ text: String) -> [String] {
+        let result = parseQueryHelperSwift(text)
+        return result
+    }
+}
+
+actor QueryStoreSwift {
+}
+
+extension QueryParserSwift {
+    func extraQuerySwift() {}
+}
@@ -0,0 +1 @@
+﻿parse_bom_py = 1
@@ -1,6 +1,13 @@
-use grepdef::{Args, SearchResult};
+use clap::Parser;
+use grepdef::{
+    Args, DefinitionKind, Error, FileType, OutputFormat, SearchMethod, SearchResult, SearchWarning,
+    Searcher, SortBy,
+};
 use rstest::rstest;
+use std::io::Write;
 use std::num::NonZero;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
 
 mod common;
 
@@ -18,6 +25,29 @@ fn search_returns_matching_js_function_line_with_args_new() {
     assert_eq!(expected, actual);
 }
 
+#[rstest]
+fn search_returns_positions_and_kinds_for_json_format() {
+    let file_path = common::get_default_fixture_for_file_type_string("js").unwrap();
+    let query = String::from("parseQuery");
+    let mut expected = vec![common::get_expected_search_result_for_file_type("js")];
+    expected[0].column = Some(10);
+    expected[0].byte_offset = Some(133);
+    expected[0].kind = Some(DefinitionKind::Function);
+    let mut args = Args::new(query, Some("js".into()), Some(vec![file_path]), false);
+    args.format = Some(OutputFormat::Json);
+    assert_eq!(expected, common::do_search(args));
+}
+
+#[rstest]
+fn search_result_to_json_includes_all_fields() {
+    let mut result = common::get_expected_search_result_for_file_type("js");
+    result.column = Some(10);
+    result.byte_offset = Some(133);
+    result.kind = Some(DefinitionKind::Function);
+    let expected = r#"{"file_path":"./tests/fixtures/by-language/js-fixture.js","line_number":7,"column":10,"byte_offset":133,"symbol":"parseQuery","kind":"function","text":"function parseQuery() {"}"#;
+    assert_eq!(expected, result.to_json());
+}
+
 #[rstest]
 fn search_returns_nothing_for_no_results() {
     let file_path = common::get_default_fixture_for_file_type_string("js").unwrap();
@@ -28,6 +58,47 @@ fn search_returns_nothing_for_no_results() {
     assert_eq!(expected, common::do_search(args));
 }
 
+#[rstest]
+#[case(String::from("localQueryPhp"), String::from("php"))]
+#[case(String::from("SwitchCasePhp"), String::from("php"))]
+#[case(String::from("parse_query_helper_py"), String::from("py"))]
+#[case(String::from("parseQueryHelperGo"), String::from("go"))]
+#[case(String::from("QueryKindGo"), String::from("go"))]
+#[case(String::from("splitQueryJava"), String::from("java"))]
+#[case(String::from("parseQueryHelperKt"), String::from("kt"))]
+#[case(String::from("localQueryKt"), String::from("kt"))]
+#[case(String::from("parse_query_cpp"), String::from("c"))]
+#[case(String::from("query_helper_cpp"), String::from("c"))]
+#[case(String::from("query_count_rb"), String::from("rb"))]
+#[case(String::from("parse_query_helper_rb"), String::from("rb"))]
+#[case(String::from("local_query_rb"), String::from("rb"))]
+#[case(String::from("ParseQueryHelperCs"), String::from("cs"))]
+#[case(String::from("SplitQueryCs"), String::from("cs"))]
+#[case(String::from("localQueryCs"), String::from("cs"))]
+#[case(String::from("parseQueryHelperSwift"), String::from("swift"))]
+#[case(String::from("fetchHelperDart"), String::from("dart"))]
+#[case(String::from("parseQueryHelperDart"), String::from("dart"))]
+#[case(String::from("parseQueryHelperScala"), String::from("scala"))]
+#[case(String::from("QueryLimitGo"), String::from("go"))]
+#[case(String::from("computeQueryGo"), String::from("go"))]
+#[case(String::from("DECLARE_QUERY_CPP"), String::from("c"))]
+#[case(String::from("compute_query_cpp"), String::from("c"))]
+#[case(String::from("make_query_error_cpp"), String::from("c"))]
+#[case(String::from("co_query_cpp"), String::from("c"))]
+#[case(String::from("release_query_cpp"), String::from("c"))]
+#[case(String::from("queryCachePhp"), String::from("php"))]
+#[case(String::from("queryTotalPhp"), String::from("php"))]
+fn search_returns_nothing_for_non_definitions(
+    #[case] query: String,
+    #[case] file_type_string: String,
+) {
+    let file_path =
+        common::get_default_fixture_for_file_type_string(file_type_string.as_str()).unwrap();
+    let expected: Vec<SearchResult> = vec![];
+    let args = common::make_args(query, Some(file_path), Some(file_type_string));
+    assert_eq!(expected, common::do_search(args));
+}
+
 #[rstest]
 fn search_returns_matching_js_function_line_with_one_thread() {
     let file_path = common::get_default_fixture_for_file_type_string("js").unwrap();
@@ -81,6 +152,16 @@ fn search_returns_matching_js_function_line_with_one_file_one_directory_matching
 #[case(String::from("parseQuery"), String::from("js"))]
 #[case(String::from("parseQuery"), String::from("php"))]
 #[case(String::from("query_db"), String::from("rs"))]
+#[case(String::from("query_db_py"), String::from("py"))]
+#[case(String::from("queryDbGo"), String::from("go"))]
+#[case(String::from("parseQueryJava"), String::from("java"))]
+#[case(String::from("queryDbKt"), String::from("kt"))]
+#[case(String::from("count_queries_cpp"), String::from("c"))]
+#[case(String::from("query_db_rb"), String::from("rb"))]
+#[case(String::from("ParseQueryCs"), String::from("cs"))]
+#[case(String::from("queryDbSwift"), String::from("swift"))]
+#[case(String::from("queryDbDart"), String::from("dart"))]
+#[case(String::from("queryDbScala"), String::from("scala"))]
 fn search_returns_matching_function_line_guessing_file_type_from_file_name(
     #[case] query: String,
     #[case] file_type_string: String,
@@ -98,6 +179,16 @@ fn search_returns_matching_function_line_guessing_file_type_from_file_name(
 #[case(String::from("parseQuery"), String::from("js"))]
 #[case(String::from("parseQuery"), String::from("php"))]
 #[case(String::from("query_db"), String::from("rs"))]
+#[case(String::from("query_db_py"), String::from("py"))]
+#[case(String::from("queryDbGo"), String::from("go"))]
+#[case(String::from("parseQueryJava"), String::from("java"))]
+#[case(String::from("queryDbKt"), String::from("kt"))]
+#[case(String::from("count_queries_cpp"), String::from("c"))]
+#[case(String::from("query_db_rb"), String::from("rb"))]
+#[case(String::from("ParseQueryCs"), String::from("cs"))]
+#[case(String::from("queryDbSwift"), String::from("swift"))]
+#[case(String::from("queryDbDart"), String::from("dart"))]
+#[case(String::from("queryDbScala"), String::from("scala"))]
 fn search_returns_matching_function_line(#[case] query: String, #[case] file_type_string: String) {
     let file_path =
         common::get_default_fixture_for_file_type_string(file_type_string.as_str()).unwrap();
@@ -164,6 +255,24 @@ fn search_returns_matching_js_function_line_with_filetype_alias(#[case] file_typ
 #[case(String::from("MyEnum"), String::from("php"), 20)]
 #[case(String::from("doSomething"), String::from("php"), 24)]
 #[case(String::from("doSomethingAbsolute"), String::from("php"), 26)]
+#[case(String::from("MAX_QUERIES_PHP"), String::from("php"), 30)]
+#[case(String::from("QUERY_PREFIX_PHP"), String::from("php"), 31)]
+#[case(String::from("QUERY_SUFFIX_PHP"), String::from("php"), 32)]
+#[case(String::from("AbstractQueryPhp"), String::from("php"), 34)]
+#[case(String::from("DEFAULT_LIMIT_PHP"), String::from("php"), 35)]
+#[case(String::from("TYPED_LIMIT_PHP"), String::from("php"), 36)]
+#[case(String::from("queryNamePhp"), String::from("php"), 37)]
+#[case(String::from("queryCountPhp"), String::from("php"), 38)]
+#[case(String::from("queryIdPhp"), String::from("php"), 39)]
+#[case(String::from("legacyQueryPhp"), String::from("php"), 40)]
+#[case(String::from("promotedQueryPhp"), String::from("php"), 42)]
+#[case(String::from("runQueryPhp"), String::from("php"), 46)]
+#[case(String::from("getQueryRefPhp"), String::from("php"), 48)]
+#[case(String::from("FinalQueryPhp"), String::from("php"), 52)]
+#[case(String::from("ReadonlyQueryPhp"), String::from("php"), 55)]
+#[case(String::from("QueryStatusPhp"), String::from("php"), 58)]
+#[case(String::from("ActivePhp"), String::from("php"), 59)]
+#[case(String::from("InactivePhp"), String::from("php"), 60)]
 #[case(String::from("query_db"), String::from("rs"), 1)]
 #[case(String::from("public_func"), String::from("rs"), 6)]
 #[case(String::from("Wrapper"), String::from("rs"), 4)]
@@ -171,6 +280,157 @@ fn search_returns_matching_js_function_line_with_filetype_alias(#[case] file_typ
 #[case(String::from("ContainerWithBlock"), String::from("rs"), 11)]
 #[case(String::from("FileType"), String::from("rs"), 19)]
 #[case(String::from("search_file"), String::from("rs"), 29)]
+#[case(String::from("MAX_SIZE_RS"), String::from("rs"), 35)]
+#[case(String::from("GLOBAL_RS"), String::from("rs"), 36)]
+#[case(String::from("COUNTER_RS"), String::from("rs"), 37)]
+#[case(String::from("AliasRs"), String::from("rs"), 38)]
+#[case(String::from("UnionRs"), String::from("rs"), 40)]
+#[case(String::from("macro_rs"), String::from("rs"), 44)]
+#[case(String::from("qualified_fn_rs"), String::from("rs"), 48)]
+#[case(String::from("const_fn_rs"), String::from("rs"), 49)]
+#[case(String::from("extern_fn_rs"), String::from("rs"), 50)]
+#[case(String::from("spaced_fn_rs"), String::from("rs"), 51)]
+#[case(String::from("GenericRs"), String::from("rs"), 53)]
+#[case(String::from("TraitRs"), String::from("rs"), 54)]
+#[case(String::from("query_db_py"), String::from("py"), 1)]
+#[case(String::from("fetch_query_py"), String::from("py"), 7)]
+#[case(String::from("QueryParserPy"), String::from("py"), 10)]
+#[case(String::from("parse_query_py"), String::from("py"), 11)]
+#[case(String::from("parse_query_async_py"), String::from("py"), 15)]
+#[case(String::from("MAX_QUERIES_PY"), String::from("py"), 18)]
+#[case(String::from("QueryIdPy"), String::from("py"), 19)]
+#[case(String::from("QueryListPy"), String::from("py"), 20)]
+#[case(String::from("result_py"), String::from("py"), 22)]
+#[case(String::from("queryDbGo"), String::from("go"), 3)]
+#[case(String::from("parseQueryGo"), String::from("go"), 6)]
+#[case(String::from("mapQueriesGo"), String::from("go"), 11)]
+#[case(String::from("QueryParserGo"), String::from("go"), 15)]
+#[case(String::from("QuerierGo"), String::from("go"), 19)]
+#[case(String::from("MaxQueriesGo"), String::from("go"), 23)]
+#[case(String::from("defaultQueryGo"), String::from("go"), 25)]
+#[case(String::from("QueryKindOneGo"), String::from("go"), 28)]
+#[case(String::from("QueryKindTwoGo"), String::from("go"), 29)]
+#[case(String::from("queryCountGo"), String::from("go"), 33)]
+#[case(String::from("queryNameGo"), String::from("go"), 34)]
+#[case(String::from("parseQueryJava"), String::from("java"), 12)]
+#[case(String::from("toString"), String::from("java"), 17)]
+#[case(String::from("convertQueryJava"), String::from("java"), 22)]
+#[case(String::from("countQueriesJava"), String::from("java"), 26)]
+#[case(String::from("formatQueryJava"), String::from("java"), 30)]
+#[case(String::from("QuerierJava"), String::from("java"), 35)]
+#[case(String::from("runQueryJava"), String::from("java"), 36)]
+#[case(String::from("QueryKindJava"), String::from("java"), 39)]
+#[case(String::from("QueryRecordJava"), String::from("java"), 44)]
+#[case(String::from("QueryAnnotationJava"), String::from("java"), 46)]
+#[case(String::from("MAX_QUERIES_KT"), String::from("kt"), 3)]
+#[case(String::from("defaultQueryKt"), String::from("kt"), 4)]
+#[case(String::from("queryCountKt"), String::from("kt"), 5)]
+#[case(String::from("QueryListKt"), String::from("kt"), 7)]
+#[case(String::from("queryDbKt"), String::from("kt"), 9)]
+#[case(String::from("convertQueryKt"), String::from("kt"), 11)]
+#[case(String::from("toQueryKt"), String::from("kt"), 13)]
+#[case(String::from("fetchQueryKt"), String::from("kt"), 15)]
+#[case(String::from("QueryResultKt"), String::from("kt"), 19)]
+#[case(String::from("QueryParserKt"), String::from("kt"), 21)]
+#[case(String::from("parseQueryKt"), String::from("kt"), 22)]
+#[case(String::from("validateQueryKt"), String::from("kt"), 27)]
+#[case(String::from("QueryFactoryKt"), String::from("kt"), 29)]
+#[case(String::from("QueryCacheKt"), String::from("kt"), 34)]
+#[case(String::from("QuerierKt"), String::from("kt"), 36)]
+#[case(String::from("runQueryKt"), String::from("kt"), 37)]
+#[case(String::from("QueryCallbackKt"), String::from("kt"), 40)]
+#[case(String::from("QueryKindKt"), String::from("kt"), 44)]
+#[case(String::from("QueryErrorKt"), String::from("kt"), 49)]
+#[case(String::from("QUERY_MAX_CPP"), String::from("c"), 3)]
+#[case(String::from("query_square_cpp"), String::from("c"), 4)]
+#[case(String::from("query_callback_cpp"), String::from("c"), 6)]
+#[case(String::from("QueryIdCpp"), String::from("c"), 7)]
+#[case(String::from("QueryListCpp"), String::from("c"), 9)]
+#[case(String::from("query_space_cpp"), String::from("c"), 11)]
+#[case(String::from("QueryNodeCpp"), String::from("c"), 13)]
+#[case(String::from("QueryValueCpp"), String::from("c"), 17)]
+#[case(String::from("QueryStateCpp"), String::from("c"), 24)]
+#[case(String::from("QueryColorCpp"), String::from("c"), 26)]
+#[case(String::from("QueryKindCpp"), String::from("c"), 28)]
+#[case(String::from("count_queries_cpp"), String::from("c"), 35)]
+#[case(String::from("format_query_cpp"), String::from("c"), 40)]
+#[case(String::from("max_query_cpp"), String::from("c"), 49)]
+#[case(String::from("run_query_cpp"), String::from("c"), 56)]
+#[case(String::from("check_query_cpp"), String::from("c"), 65)]
+#[case(String::from("reset_query_cpp"), String::from("c"), 70)]
+#[case(String::from("MAX_QUERIES_RB"), String::from("rb"), 3)]
+#[case(String::from("query_db_rb"), String::from("rb"), 6)]
+#[case(String::from("top_query_rb"), String::from("rb"), 10)]
+#[case(String::from("QueryHelpersRb"), String::from("rb"), 13)]
+#[case(String::from("DEFAULT_QUERY_RB"), String::from("rb"), 14)]
+#[case(String::from("helper_query_rb"), String::from("rb"), 16)]
+#[case(String::from("QueryParserRb"), String::from("rb"), 21)]
+#[case(String::from("query_text_rb"), String::from("rb"), 24)]
+#[case(String::from("query_size_rb"), String::from("rb"), 24)]
+#[case(String::from("query_id_rb"), String::from("rb"), 25)]
+#[case(String::from("build_query_rb"), String::from("rb"), 31)]
+#[case(String::from("parse_query_rb"), String::from("rb"), 35)]
+#[case(String::from("run_query_rb"), String::from("rb"), 40)]
+#[case(String::from("dynamic_query_rb"), String::from("rb"), 43)]
+#[case(String::from("old_query_rb"), String::from("rb"), 44)]
+#[case(String::from("QueryAdminRb"), String::from("rb"), 47)]
+#[case(String::from("QueryToolsCs"), String::from("cs"), 3)]
+#[case(String::from("QueryFilterCs"), String::from("cs"), 5)]
+#[case(String::from("IQuerierCs"), String::from("cs"), 7)]
+#[case(String::from("QueryKindCs"), String::from("cs"), 12)]
+#[case(String::from("QueryPointCs"), String::from("cs"), 18)]
+#[case(String::from("QueryRecordCs"), String::from("cs"), 23)]
+#[case(String::from("QuerySizeCs"), String::from("cs"), 33)]
+#[case(String::from("QueryNameCs"), String::from("cs"), 35)]
+#[case(String::from("QueryListCs"), String::from("cs"), 37)]
+#[case(String::from("ParseQueryCs"), String::from("cs"), 42)]
+#[case(String::from("FetchQueryCs"), String::from("cs"), 55)]
+#[case(String::from("ResetQueryCs"), String::from("cs"), 57)]
+#[case(String::from("maxQueriesSwift"), String::from("swift"), 3)]
+#[case(String::from("queryCountSwift"), String::from("swift"), 4)]
+#[case(String::from("QueryIdSwift"), String::from("swift"), 6)]
+#[case(String::from("queryDbSwift"), String::from("swift"), 8)]
+#[case(String::from("QuerierSwift"), String::from("swift"), 12)]
+#[case(String::from("QueryResultSwift"), String::from("swift"), 13)]
+#[case(String::from("runQuerySwift"), String::from("swift"), 14)]
+#[case(String::from("QueryKindSwift"), String::from("swift"), 17)]
+#[case(String::from("QueryPointSwift"), String::from("swift"), 21)]
+#[case(String::from("QueryParserSwift"), String::from("swift"), 25)]
+#[case(String::from("querySizeSwift"), String::from("swift"), 26)]
+#[case(String::from("parseQuerySwift"), String::from("swift"), 29)]
+#[case(String::from("QueryStoreSwift"), String::from("swift"), 35)]
+#[case(String::from("extraQuerySwift"), String::from("swift"), 39)]
+#[case(String::from("QueryCallbackDart"), String::from("dart"), 3)]
+#[case(String::from("LegacyCallbackDart"), String::from("dart"), 4)]
+#[case(String::from("queryDbDart"), String::from("dart"), 6)]
+#[case(String::from("fetchQueriesDart"), String::from("dart"), 8)]
+#[case(String::from("QueryMixinDart"), String::from("dart"), 12)]
+#[case(String::from("mixinQueryDart"), String::from("dart"), 13)]
+#[case(String::from("QueryKindDart"), String::from("dart"), 16)]
+#[case(String::from("QueryParserDart"), String::from("dart"), 18)]
+#[case(String::from("parseQueryDart"), String::from("dart"), 23)]
+#[case(String::from("countQueriesDart"), String::from("dart"), 28)]
+#[case(String::from("QueryExtDart"), String::from("dart"), 31)]
+#[case(String::from("extraQueryDart"), String::from("dart"), 32)]
+#[case(String::from("queryDbScala"), String::from("scala"), 3)]
+#[case(String::from("QuerierScala"), String::from("scala"), 5)]
+#[case(String::from("QueryResultScala"), String::from("scala"), 9)]
+#[case(String::from("parseQueryScala"), String::from("scala"), 12)]
+#[case(String::from("maxQueriesScala"), String::from("scala"), 21)]
+#[case(String::from("queryCountScala"), String::from("scala"), 22)]
+#[case(String::from("QueryIdScala"), String::from("scala"), 23)]
+#[case(String::from("QueryEmptyScala"), String::from("scala"), 26)]
+#[case(String::from("QueryKindScala"), String::from("scala"), 28)]
+#[case(String::from("QueryOptionsGo"), String::from("go"), 44)]
+#[case(String::from("QueryIdGo"), String::from("go"), 47)]
+#[case(String::from("QueryStartGo"), String::from("go"), 51)]
+#[case(String::from("QueryComputedGo"), String::from("go"), 52)]
+#[case(String::from("QuerySeparatorGo"), String::from("go"), 55)]
+#[case(String::from("QueryEndGo"), String::from("go"), 56)]
+#[case(String::from("QueryRunnerCpp"), String::from("c"), 76)]
+#[case(String::from("next_query_cpp"), String::from("c"), 79)]
+#[case(String::from("staticQueryPhp"), String::from("php"), 75)]
+#[case(String::from("staticUnionPhp"), String::from("php"), 76)]
 fn search_returns_expected_line_number_for_file_type(
     #[case] query: String,
     #[case] file_type_string: String,
@@ -181,7 +441,7 @@ fn search_returns_expected_line_number_for_file_type(
     let args = common::make_args(query, Some(file_path), Some(file_type_string));
     let actual = common::do_search(args);
     assert_eq!(1, actual.len());
-    let first_actual = actual.get(0).expect("Search failed for test");
+    let first_actual = actual.first().expect("Search failed for test");
     assert_eq!(line_number, first_actual.line_number.unwrap());
 }
 
@@ -241,8 +501,12 @@ fn search_returns_matching_php_function_line_guessing_file_type_from_directory()
     let query = String::from("otherPhpFunction");
     let line_number = Some(3);
     let expected = vec![SearchResult {
-        file_path: file_path.clone(),
+        file_path: file_path.clone().into(),
         line_number,
+        column: None,
+        byte_offset: None,
+        symbol: String::from("otherPhpFunction"),
+        kind: None,
         text: String::from("function otherPhpFunction() {"),
     }];
     let args = common::make_args(query, Some(String::from("./tests/fixtures/only-php")), None);
@@ -253,6 +517,16 @@ fn search_returns_matching_php_function_line_guessing_file_type_from_directory()
 #[case(String::from("parseQuery"), String::from("js"))]
 #[case(String::from("parseQuery"), String::from("php"))]
 #[case(String::from("query_db"), String::from("rs"))]
+#[case(String::from("query_db_py"), String::from("py"))]
+#[case(String::from("queryDbGo"), String::from("go"))]
+#[case(String::from("parseQueryJava"), String::from("java"))]
+#[case(String::from("queryDbKt"), String::from("kt"))]
+#[case(String::from("count_queries_cpp"), String::from("c"))]
+#[case(String::from("query_db_rb"), String::from("rb"))]
+#[case(String::from("ParseQueryCs"), String::from("cs"))]
+#[case(String::from("queryDbSwift"), String::from("swift"))]
+#[case(String::from("queryDbDart"), String::from("dart"))]
+#[case(String::from("queryDbScala"), String::from("scala"))]
 fn search_returns_matching_function_line_for_recursive(
     #[case] query: String,
     #[case] file_type_string: String,
@@ -268,3 +542,1429 @@ fn search_returns_matching_function_line_for_recursive(
     // Note that there may be more results than was expected, but we're ok with that here.
     assert!(expected.iter().all(|item| actual.contains(item)));
 }
+
+#[rstest]
+#[case(vec![String::from("js"), String::from("php")])]
+#[case(vec![String::from("all")])]
+fn search_returns_matching_function_lines_for_multiple_file_types(
+    #[case] file_type_strings: Vec<String>,
+) {
+    let file_path = String::from("./tests/fixtures");
+    let query = String::from("parseQuery");
+    let expected = vec![
+        common::get_expected_search_result_for_file_type("js"),
+        common::get_expected_search_result_for_file_type("jsx"),
+        common::get_expected_search_result_for_file_type("php"),
+    ];
+    let mut args = common::make_args(query, Some(file_path), None);
+    args.file_type = Some(file_type_strings);
+    let actual = common::do_search(args);
+    println!("expected {:?}", expected);
+    println!("actual   {:?}", actual);
+    assert!(actual.iter().all(|item| expected.contains(item)));
+    assert!(expected.iter().all(|item| actual.contains(item)));
+}
+
+#[rstest]
+#[case(vec!["grepdef", "--type", "js,php", "parseQuery"])]
+#[case(vec!["grepdef", "-t", "js", "-t", "php", "parseQuery"])]
+fn args_parse_multiple_file_types(#[case] cli_args: Vec<&str>) {
+    let args = Args::try_parse_from(cli_args).unwrap();
+    assert_eq!(
+        Some(vec![String::from("js"), String::from("php")]),
+        args.file_type
+    );
+}
+
+#[rstest]
+#[case(String::from("ContainerWithBlock"), vec![11, 15, 57])]
+#[case(String::from("FileType"), vec![19, 25])]
+#[case(String::from("GenericRs"), vec![53, 56])]
+#[case(String::from("TraitRs"), vec![54])]
+#[case(String::from("Display"), vec![])]
+fn search_returns_rs_impl_blocks_if_included(
+    #[case] query: String,
+    #[case] line_numbers: Vec<usize>,
+) {
+    let file_path = common::get_default_fixture_for_file_type_string("rs").unwrap();
+    let mut args = common::make_args(query, Some(file_path), Some(String::from("rs")));
+    args.include_impl = true;
+    args.threads = Some(NonZero::new(1).unwrap());
+    let actual: Vec<usize> = common::do_search(args)
+        .iter()
+        .map(|result| result.line_number.unwrap())
+        .collect();
+    assert_eq!(line_numbers, actual);
+}
+
+#[rstest]
+#[case(String::from("QueryParserSwift"), String::from("swift"), true, vec![25, 38])]
+#[case(String::from("QueryParserSwift"), String::from("swift"), false, vec![25])]
+#[case(String::from("QueryParserDart"), String::from("dart"), true, vec![18, 31])]
+#[case(String::from("QueryParserDart"), String::from("dart"), false, vec![18])]
+#[case(String::from("QueryExtDart"), String::from("dart"), true, vec![31])]
+#[case(String::from("QueryExtDart"), String::from("dart"), false, vec![31])]
+fn search_returns_extensions_if_included(
+    #[case] query: String,
+    #[case] file_type_string: String,
+    #[case] include_impl: bool,
+    #[case] line_numbers: Vec<usize>,
+) {
+    let file_path =
+        common::get_default_fixture_for_file_type_string(file_type_string.as_str()).unwrap();
+    let mut args = common::make_args(query, Some(file_path), Some(file_type_string));
+    args.include_impl = include_impl;
+    let actual: Vec<usize> = common::do_search(args)
+        .iter()
+        .map(|result| result.line_number.unwrap())
+        .collect();
+    assert_eq!(line_numbers, actual);
+}
+
+#[rstest]
+fn search_returns_matching_php_function_line_in_namespace() {
+    let file_path = String::from("./tests/fixtures/by-language/php-namespace-fixture.php");
+    let query = String::from("namespacedQueryPhp");
+    let expected = vec![SearchResult {
+        file_path: file_path.clone().into(),
+        line_number: Some(4),
+        column: None,
+        byte_offset: None,
+        symbol: query.clone(),
+        kind: None,
+        text: String::from("function namespacedQueryPhp(): void {"),
+    }];
+    let args = common::make_args(query, Some(file_path), Some(String::from("php")));
+    assert_eq!(expected, common::do_search(args));
+}
+
+#[rstest]
+#[case(String::from("queryDb"), String::from("js"), DefinitionKind::Function)]
+#[case(String::from("makeQuery"), String::from("js"), DefinitionKind::Const)]
+#[case(
+    String::from("shorthandFunction"),
+    String::from("js"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("longhandFunction"),
+    String::from("js"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("longhandArrowFunction"),
+    String::from("js"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("longhandProperty"),
+    String::from("js"),
+    DefinitionKind::Property
+)]
+#[case(
+    String::from("longhandArrowFunctionTS"),
+    String::from("ts"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("AnInterface"),
+    String::from("ts"),
+    DefinitionKind::Interface
+)]
+#[case(String::from("AType"), String::from("ts"), DefinitionKind::Type)]
+#[case(
+    String::from("TypeDefObject"),
+    String::from("ts"),
+    DefinitionKind::Type
+)]
+#[case(String::from("queryDb"), String::from("php"), DefinitionKind::Function)]
+#[case(String::from("Foo"), String::from("php"), DefinitionKind::Class)]
+#[case(String::from("Bar"), String::from("php"), DefinitionKind::Trait)]
+#[case(String::from("Zoom"), String::from("php"), DefinitionKind::Interface)]
+#[case(String::from("MyEnum"), String::from("php"), DefinitionKind::Enum)]
+#[case(
+    String::from("doSomething"),
+    String::from("php"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QUERY_PREFIX_PHP"),
+    String::from("php"),
+    DefinitionKind::Const
+)]
+#[case(
+    String::from("queryNamePhp"),
+    String::from("php"),
+    DefinitionKind::Property
+)]
+#[case(String::from("ActivePhp"), String::from("php"), DefinitionKind::Const)]
+#[case(String::from("query_db"), String::from("rs"), DefinitionKind::Function)]
+#[case(String::from("Wrapper"), String::from("rs"), DefinitionKind::Module)]
+#[case(
+    String::from("ContainerWithBlock"),
+    String::from("rs"),
+    DefinitionKind::Struct
+)]
+#[case(String::from("FileType"), String::from("rs"), DefinitionKind::Enum)]
+#[case(String::from("MAX_SIZE_RS"), String::from("rs"), DefinitionKind::Const)]
+#[case(
+    String::from("GLOBAL_RS"),
+    String::from("rs"),
+    DefinitionKind::Variable
+)]
+#[case(String::from("AliasRs"), String::from("rs"), DefinitionKind::Type)]
+#[case(String::from("UnionRs"), String::from("rs"), DefinitionKind::Struct)]
+#[case(String::from("macro_rs"), String::from("rs"), DefinitionKind::Macro)]
+#[case(String::from("TraitRs"), String::from("rs"), DefinitionKind::Trait)]
+#[case(
+    String::from("query_db_py"),
+    String::from("py"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("QueryParserPy"),
+    String::from("py"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("parse_query_py"),
+    String::from("py"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("MAX_QUERIES_PY"),
+    String::from("py"),
+    DefinitionKind::Variable
+)]
+#[case(String::from("QueryIdPy"), String::from("py"), DefinitionKind::Type)]
+#[case(String::from("QueryListPy"), String::from("py"), DefinitionKind::Type)]
+#[case(
+    String::from("queryDbGo"),
+    String::from("go"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("parseQueryGo"),
+    String::from("go"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryParserGo"),
+    String::from("go"),
+    DefinitionKind::Struct
+)]
+#[case(
+    String::from("QuerierGo"),
+    String::from("go"),
+    DefinitionKind::Interface
+)]
+#[case(
+    String::from("MaxQueriesGo"),
+    String::from("go"),
+    DefinitionKind::Const
+)]
+#[case(
+    String::from("defaultQueryGo"),
+    String::from("go"),
+    DefinitionKind::Variable
+)]
+#[case(
+    String::from("QueryKindTwoGo"),
+    String::from("go"),
+    DefinitionKind::Const
+)]
+#[case(
+    String::from("queryNameGo"),
+    String::from("go"),
+    DefinitionKind::Variable
+)]
+#[case(
+    String::from("parseQueryJava"),
+    String::from("java"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QuerierJava"),
+    String::from("java"),
+    DefinitionKind::Interface
+)]
+#[case(
+    String::from("QueryKindJava"),
+    String::from("java"),
+    DefinitionKind::Enum
+)]
+#[case(
+    String::from("QueryRecordJava"),
+    String::from("java"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("QueryAnnotationJava"),
+    String::from("java"),
+    DefinitionKind::Interface
+)]
+#[case(
+    String::from("MAX_QUERIES_KT"),
+    String::from("kt"),
+    DefinitionKind::Const
+)]
+#[case(
+    String::from("defaultQueryKt"),
+    String::from("kt"),
+    DefinitionKind::Variable
+)]
+#[case(String::from("QueryListKt"), String::from("kt"), DefinitionKind::Type)]
+#[case(
+    String::from("queryDbKt"),
+    String::from("kt"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("toQueryKt"),
+    String::from("kt"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("QueryResultKt"),
+    String::from("kt"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("parseQueryKt"),
+    String::from("kt"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryCacheKt"),
+    String::from("kt"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("QuerierKt"),
+    String::from("kt"),
+    DefinitionKind::Interface
+)]
+#[case(
+    String::from("QueryCallbackKt"),
+    String::from("kt"),
+    DefinitionKind::Interface
+)]
+#[case(String::from("QueryKindKt"), String::from("kt"), DefinitionKind::Enum)]
+#[case(
+    String::from("QUERY_MAX_CPP"),
+    String::from("c"),
+    DefinitionKind::Macro
+)]
+#[case(
+    String::from("query_square_cpp"),
+    String::from("c"),
+    DefinitionKind::Macro
+)]
+#[case(
+    String::from("query_callback_cpp"),
+    String::from("c"),
+    DefinitionKind::Type
+)]
+#[case(String::from("QueryIdCpp"), String::from("c"), DefinitionKind::Type)]
+#[case(String::from("QueryListCpp"), String::from("c"), DefinitionKind::Type)]
+#[case(
+    String::from("query_space_cpp"),
+    String::from("c"),
+    DefinitionKind::Module
+)]
+#[case(
+    String::from("QueryNodeCpp"),
+    String::from("c"),
+    DefinitionKind::Struct
+)]
+#[case(
+    String::from("QueryValueCpp"),
+    String::from("c"),
+    DefinitionKind::Struct
+)]
+#[case(String::from("QueryStateCpp"), String::from("c"), DefinitionKind::Type)]
+#[case(String::from("QueryColorCpp"), String::from("c"), DefinitionKind::Enum)]
+#[case(String::from("QueryKindCpp"), String::from("c"), DefinitionKind::Enum)]
+#[case(
+    String::from("count_queries_cpp"),
+    String::from("c"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("format_query_cpp"),
+    String::from("c"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("max_query_cpp"),
+    String::from("c"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("run_query_cpp"),
+    String::from("c"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("check_query_cpp"),
+    String::from("c"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("reset_query_cpp"),
+    String::from("c"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("MAX_QUERIES_RB"),
+    String::from("rb"),
+    DefinitionKind::Const
+)]
+#[case(
+    String::from("query_db_rb"),
+    String::from("rb"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("top_query_rb"),
+    String::from("rb"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryHelpersRb"),
+    String::from("rb"),
+    DefinitionKind::Module
+)]
+#[case(
+    String::from("DEFAULT_QUERY_RB"),
+    String::from("rb"),
+    DefinitionKind::Const
+)]
+#[case(
+    String::from("helper_query_rb"),
+    String::from("rb"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryParserRb"),
+    String::from("rb"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("query_text_rb"),
+    String::from("rb"),
+    DefinitionKind::Property
+)]
+#[case(
+    String::from("query_size_rb"),
+    String::from("rb"),
+    DefinitionKind::Property
+)]
+#[case(
+    String::from("query_id_rb"),
+    String::from("rb"),
+    DefinitionKind::Property
+)]
+#[case(
+    String::from("build_query_rb"),
+    String::from("rb"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("parse_query_rb"),
+    String::from("rb"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("run_query_rb"),
+    String::from("rb"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("dynamic_query_rb"),
+    String::from("rb"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("old_query_rb"),
+    String::from("rb"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryAdminRb"),
+    String::from("rb"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("QueryToolsCs"),
+    String::from("cs"),
+    DefinitionKind::Module
+)]
+#[case(
+    String::from("QueryFilterCs"),
+    String::from("cs"),
+    DefinitionKind::Type
+)]
+#[case(
+    String::from("IQuerierCs"),
+    String::from("cs"),
+    DefinitionKind::Interface
+)]
+#[case(String::from("QueryKindCs"), String::from("cs"), DefinitionKind::Enum)]
+#[case(
+    String::from("QueryPointCs"),
+    String::from("cs"),
+    DefinitionKind::Struct
+)]
+#[case(
+    String::from("QueryRecordCs"),
+    String::from("cs"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("QuerySizeCs"),
+    String::from("cs"),
+    DefinitionKind::Property
+)]
+#[case(
+    String::from("QueryNameCs"),
+    String::from("cs"),
+    DefinitionKind::Property
+)]
+#[case(
+    String::from("QueryListCs"),
+    String::from("cs"),
+    DefinitionKind::Property
+)]
+#[case(
+    String::from("ParseQueryCs"),
+    String::from("cs"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("FetchQueryCs"),
+    String::from("cs"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("ResetQueryCs"),
+    String::from("cs"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("maxQueriesSwift"),
+    String::from("swift"),
+    DefinitionKind::Variable
+)]
+#[case(
+    String::from("queryCountSwift"),
+    String::from("swift"),
+    DefinitionKind::Variable
+)]
+#[case(
+    String::from("QueryIdSwift"),
+    String::from("swift"),
+    DefinitionKind::Type
+)]
+#[case(
+    String::from("queryDbSwift"),
+    String::from("swift"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("QuerierSwift"),
+    String::from("swift"),
+    DefinitionKind::Interface
+)]
+#[case(
+    String::from("QueryResultSwift"),
+    String::from("swift"),
+    DefinitionKind::Type
+)]
+#[case(
+    String::from("runQuerySwift"),
+    String::from("swift"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryKindSwift"),
+    String::from("swift"),
+    DefinitionKind::Enum
+)]
+#[case(
+    String::from("QueryPointSwift"),
+    String::from("swift"),
+    DefinitionKind::Struct
+)]
+#[case(
+    String::from("QueryParserSwift"),
+    String::from("swift"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("querySizeSwift"),
+    String::from("swift"),
+    DefinitionKind::Variable
+)]
+#[case(
+    String::from("parseQuerySwift"),
+    String::from("swift"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryStoreSwift"),
+    String::from("swift"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("extraQuerySwift"),
+    String::from("swift"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryCallbackDart"),
+    String::from("dart"),
+    DefinitionKind::Type
+)]
+#[case(
+    String::from("LegacyCallbackDart"),
+    String::from("dart"),
+    DefinitionKind::Type
+)]
+#[case(
+    String::from("queryDbDart"),
+    String::from("dart"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("fetchQueriesDart"),
+    String::from("dart"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("QueryMixinDart"),
+    String::from("dart"),
+    DefinitionKind::Trait
+)]
+#[case(
+    String::from("mixinQueryDart"),
+    String::from("dart"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryKindDart"),
+    String::from("dart"),
+    DefinitionKind::Enum
+)]
+#[case(
+    String::from("QueryParserDart"),
+    String::from("dart"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("parseQueryDart"),
+    String::from("dart"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("countQueriesDart"),
+    String::from("dart"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("QueryExtDart"),
+    String::from("dart"),
+    DefinitionKind::Impl
+)]
+#[case(
+    String::from("extraQueryDart"),
+    String::from("dart"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("queryDbScala"),
+    String::from("scala"),
+    DefinitionKind::Function
+)]
+#[case(
+    String::from("QuerierScala"),
+    String::from("scala"),
+    DefinitionKind::Trait
+)]
+#[case(
+    String::from("QueryResultScala"),
+    String::from("scala"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("parseQueryScala"),
+    String::from("scala"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("maxQueriesScala"),
+    String::from("scala"),
+    DefinitionKind::Variable
+)]
+#[case(
+    String::from("queryCountScala"),
+    String::from("scala"),
+    DefinitionKind::Variable
+)]
+#[case(
+    String::from("QueryIdScala"),
+    String::from("scala"),
+    DefinitionKind::Type
+)]
+#[case(
+    String::from("QueryEmptyScala"),
+    String::from("scala"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("QueryKindScala"),
+    String::from("scala"),
+    DefinitionKind::Enum
+)]
+#[case(
+    String::from("QueryOptionsGo"),
+    String::from("go"),
+    DefinitionKind::Type
+)]
+#[case(String::from("QueryIdGo"), String::from("go"), DefinitionKind::Type)]
+#[case(
+    String::from("QueryStartGo"),
+    String::from("go"),
+    DefinitionKind::Const
+)]
+#[case(
+    String::from("QueryComputedGo"),
+    String::from("go"),
+    DefinitionKind::Const
+)]
+#[case(
+    String::from("QuerySeparatorGo"),
+    String::from("go"),
+    DefinitionKind::Const
+)]
+#[case(String::from("QueryEndGo"), String::from("go"), DefinitionKind::Const)]
+#[case(
+    String::from("QueryRunnerCpp"),
+    String::from("c"),
+    DefinitionKind::Class
+)]
+#[case(
+    String::from("next_query_cpp"),
+    String::from("c"),
+    DefinitionKind::Method
+)]
+#[case(
+    String::from("staticQueryPhp"),
+    String::from("php"),
+    DefinitionKind::Property
+)]
+#[case(
+    String::from("staticUnionPhp"),
+    String::from("php"),
+    DefinitionKind::Property
+)]
+fn search_returns_expected_kind_for_file_type(
+    #[case] query: String,
+    #[case] file_type_string: String,
+    #[case] kind: DefinitionKind,
+) {
+    let file_path =
+        common::get_default_fixture_for_file_type_string(file_type_string.as_str()).unwrap();
+    let mut args = common::make_args(query, Some(file_path), Some(file_type_string));
+    args.show_kind = true;
+    let actual = common::do_search(args);
+    assert_eq!(1, actual.len());
+    assert_eq!(Some(kind), actual[0].kind);
+}
+
+#[rstest]
+fn search_returns_rs_impl_kind_if_included() {
+    let file_path = common::get_default_fixture_for_file_type_string("rs").unwrap();
+    let mut args = common::make_args(
+        String::from("FileType"),
+        Some(file_path),
+        Some(String::from("rs")),
+    );
+    args.include_impl = true;
+    args.show_kind = true;
+    let actual: Vec<Option<DefinitionKind>> = common::do_search(args)
+        .into_iter()
+        .map(|result| result.kind)
+        .collect();
+    assert_eq!(
+        vec![Some(DefinitionKind::Enum), Some(DefinitionKind::Impl)],
+        actual
+    );
+}
+
+#[rstest]
+#[case(String::from("Wrapper"), String::from("rs"), vec![DefinitionKind::Module], vec![4])]
+#[case(String::from("Wrapper"), String::from("rs"), vec![DefinitionKind::Struct], vec![])]
+#[case(String::from("FileType"), String::from("rs"), vec![DefinitionKind::Impl], vec![25])]
+#[case(
+    String::from("ContainerWithBlock"),
+    String::from("rs"),
+    vec![DefinitionKind::Struct, DefinitionKind::Impl],
+    vec![11, 15, 57]
+)]
+#[case(String::from("QueryKindOneGo"), String::from("go"), vec![DefinitionKind::Const], vec![28])]
+#[case(String::from("QueryKindOneGo"), String::from("go"), vec![DefinitionKind::Variable], vec![])]
+#[case(String::from("queryCountGo"), String::from("go"), vec![DefinitionKind::Variable], vec![33])]
+#[case(String::from("parseQuery"), String::from("js"), vec![DefinitionKind::Macro], vec![])]
+fn search_returns_only_matching_kinds(
+    #[case] query: String,
+    #[case] file_type_string: String,
+    #[case] kinds: Vec<DefinitionKind>,
+    #[case] line_numbers: Vec<usize>,
+) {
+    let file_path =
+        common::get_default_fixture_for_file_type_string(file_type_string.as_str()).unwrap();
+    let mut args = common::make_args(query, Some(file_path), Some(file_type_string));
+    args.kinds = Some(kinds);
+    let actual: Vec<usize> = common::do_search(args)
+        .iter()
+        .map(|result| result.line_number.unwrap())
+        .collect();
+    assert_eq!(line_numbers, actual);
+}
+
+#[rstest]
+fn args_parse_multiple_kinds() {
+    let args = Args::try_parse_from(vec!["grepdef", "--kind", "struct,function", "Query"]).unwrap();
+    assert_eq!(
+        Some(vec![DefinitionKind::Struct, DefinitionKind::Function]),
+        args.kinds
+    );
+}
+
+#[rstest]
+#[case(String::from("parseQuery"), String::from("js"), 7, 10, 133)]
+#[case(String::from("TypeDefObject"), String::from("ts"), 66, 22, 1283)]
+#[case(String::from("longhandPropertyTS"), String::from("ts"), 43, 2, 900)]
+#[case(String::from("QueryKindTwoGo"), String::from("go"), 29, 2, 416)]
+fn search_returns_column_and_byte_offset_of_symbol(
+    #[case] query: String,
+    #[case] file_type_string: String,
+    #[case] line_number: usize,
+    #[case] column: usize,
+    #[case] byte_offset: usize,
+) {
+    let file_path =
+        common::get_default_fixture_for_file_type_string(file_type_string.as_str()).unwrap();
+    let mut args = common::make_args(query, Some(file_path), Some(file_type_string));
+    args.column = true;
+    let actual = common::do_search(args);
+    assert_eq!(1, actual.len());
+    assert_eq!(Some(line_number), actual[0].line_number);
+    assert_eq!(Some(column), actual[0].column);
+    assert_eq!(Some(byte_offset), actual[0].byte_offset);
+}
+
+#[rstest]
+fn search_returns_byte_offset_for_crlf_line_endings() {
+    let file_path = String::from("./tests/fixtures/by-language/crlf-fixture.js");
+    let mut args = common::make_args(
+        String::from("secondCrlfQuery"),
+        Some(file_path),
+        Some(String::from("js")),
+    );
+    args.column = true;
+    let actual = common::do_search(args);
+    assert_eq!(1, actual.len());
+    assert_eq!(Some(3), actual[0].line_number);
+    assert_eq!(Some(10), actual[0].column);
+    assert_eq!(Some(38), actual[0].byte_offset);
+    assert_eq!("function secondCrlfQuery() {", actual[0].text);
+}
+
+#[rstest]
+#[case(Some(OutputFormat::Vimgrep), false)]
+#[case(Some(OutputFormat::Emacs), false)]
+#[case(None, true)]
+fn search_returns_positions_for_editor_formats(
+    #[case] format: Option<OutputFormat>,
+    #[case] vimgrep: bool,
+) {
+    let file_path = common::get_default_fixture_for_file_type_string("js").unwrap();
+    let query = String::from("parseQuery");
+    let mut expected = vec![common::get_expected_search_result_for_file_type("js")];
+    expected[0].column = Some(10);
+    expected[0].byte_offset = Some(133);
+    let mut args = Args::new(query, Some("js".into()), Some(vec![file_path]), false);
+    args.format = format;
+    args.vimgrep = vimgrep;
+    assert_eq!(expected, common::do_search(args));
+}
+
+#[rstest]
+fn search_result_to_vimgrep_includes_column() {
+    let mut result = common::get_expected_search_result_for_file_type("js");
+    result.column = Some(10);
+    let expected = "./tests/fixtures/by-language/js-fixture.js:7:10:function parseQuery() {";
+    assert_eq!(expected, result.to_vimgrep());
+}
+
+#[rstest]
+fn search_result_to_emacs_includes_column() {
+    let mut result = common::get_expected_search_result_for_file_type("js");
+    result.column = Some(10);
+    let expected = "./tests/fixtures/by-language/js-fixture.js:7:10: function parseQuery() {";
+    assert_eq!(expected, result.to_emacs());
+}
+
+#[rstest]
+fn args_parse_vimgrep_as_output_format() {
+    let args = Args::try_parse_from(vec!["grepdef", "--vimgrep", "parseQuery"]).unwrap();
+    assert_eq!(OutputFormat::Vimgrep, args.output_format());
+}
+
+#[rstest]
+fn search_returns_results_sorted_by_path_and_line_number() {
+    let file_path = String::from("./tests/fixtures/only-php ./tests/fixtures");
+    let query = String::from("(parseQuery|otherPhpFunction)");
+    let mut args = common::make_args(query, Some(file_path), None);
+    args.file_type = Some(vec![String::from("all")]);
+    let actual: Vec<(PathBuf, usize)> = common::do_search(args)
+        .into_iter()
+        .map(|result| (result.file_path, result.line_number.unwrap()))
+        .collect();
+    let expected: Vec<(PathBuf, usize)> = vec![
+        ("./tests/fixtures/only-php/other-php-fixture.php", 3),
+        ("./tests/fixtures/by-language/js-fixture.js", 7),
+        ("./tests/fixtures/by-language/jsx-fixture.jsx", 7),
+        ("./tests/fixtures/by-language/php-fixture.php", 6),
+        ("./tests/fixtures/only-php/other-php-fixture.php", 3),
+    ]
+    .into_iter()
+    .map(|(file_path, line_number)| (PathBuf::from(file_path), line_number))
+    .collect();
+    assert_eq!(expected, actual);
+}
+
+#[rstest]
+#[case(SortBy::Path, vec!["./tests/fixtures/by-language/rs-fixture.rs", "./src/lib.rs"])]
+#[case(SortBy::Relevance, vec!["./src/lib.rs", "./tests/fixtures/by-language/rs-fixture.rs"])]
+fn search_returns_results_in_sort_order(#[case] sort: SortBy, #[case] expected: Vec<&str>) {
+    let file_path = String::from("./tests ./src");
+    let query = String::from("FileType");
+    let mut args = common::make_args(query, Some(file_path), Some(String::from("rs")));
+    args.kinds = Some(vec![DefinitionKind::Enum]);
+    args.sort = Some(sort);
+    let actual: Vec<PathBuf> = common::do_search(args)
+        .into_iter()
+        .map(|result| result.file_path)
+        .collect();
+    let expected: Vec<PathBuf> = expected.into_iter().map(PathBuf::from).collect();
+    assert_eq!(expected, actual);
+}
+
+#[rstest]
+fn search_returns_case_insensitive_exact_matches_by_relevance() {
+    let dir = common::make_temp_dir("relevance-ignore-case");
+    std::fs::create_dir_all(dir.join("deep/nested")).unwrap();
+    std::fs::write(
+        dir.join("deep/nested/exact.js"),
+        "function parseRankJs() {}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("upper.js"), "function PARSERANKJS() {}\n").unwrap();
+    let query = String::from("parseRankJs");
+    let mut args = common::make_args(query, dir.to_str().map(String::from), Some("js".into()));
+    args.ignore_case = true;
+    args.sort = Some(SortBy::Relevance);
+    let actual: Vec<String> = common::do_search(args)
+        .into_iter()
+        .map(|result| result.symbol)
+        .collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+    // Both symbols match the query exactly when ignoring case, so the shallower one comes first.
+    assert_eq!(vec!["PARSERANKJS", "parseRankJs"], actual);
+}
+
+#[rstest]
+#[case(SortBy::Path)]
+#[case(SortBy::Relevance)]
+#[case(SortBy::None)]
+fn search_with_passes_each_result_to_callback(#[case] sort: SortBy) {
+    let make_args = || {
+        let file_path = String::from("./tests/fixtures");
+        let query = String::from("parseQuery");
+        let mut args = common::make_args(query, Some(file_path), None);
+        args.file_type = Some(vec![String::from("all")]);
+        args.sort = Some(sort.clone());
+        args
+    };
+    let expected = common::do_search(make_args());
+    let mut actual: Vec<SearchResult> = vec![];
+    Searcher::new(make_args())
+        .unwrap()
+        .search_with(|result| actual.push(result))
+        .expect("Search failed for test");
+    if sort == SortBy::None {
+        assert_eq!(expected.len(), actual.len());
+        assert!(expected.iter().all(|result| actual.contains(result)));
+    } else {
+        assert_eq!(expected, actual);
+    }
+}
+
+#[rstest]
+#[case(SortBy::Path, 1)]
+#[case(SortBy::Path, 2)]
+#[case(SortBy::Relevance, 2)]
+#[case(SortBy::Path, 0)]
+fn search_returns_at_most_max_results(#[case] sort: SortBy, #[case] max_results: usize) {
+    let make_args = |max_results: Option<usize>| {
+        let file_path = String::from("./tests/fixtures");
+        let query = String::from("parseQuery");
+        let mut args = common::make_args(query, Some(file_path), None);
+        args.file_type = Some(vec![String::from("all")]);
+        args.sort = Some(sort.clone());
+        args.max_results = max_results;
+        args
+    };
+    let all_results = common::do_search(make_args(None));
+    let expected: Vec<SearchResult> = all_results.into_iter().take(max_results).collect();
+    let actual = common::do_search(make_args(Some(max_results)));
+    assert_eq!(expected, actual);
+}
+
+#[rstest]
+fn search_returns_max_results_when_unsorted() {
+    let file_path = String::from("./tests/fixtures");
+    let query = String::from("parseQuery");
+    let mut args = common::make_args(query, Some(file_path), None);
+    args.file_type = Some(vec![String::from("all")]);
+    args.sort = Some(SortBy::None);
+    args.max_results = Some(2);
+    let actual = common::do_search(args);
+    assert_eq!(2, actual.len());
+}
+
+#[rstest]
+fn args_parse_max_count() {
+    let args = Args::parse_from(["grepdef", "-m", "1", "parseQuery"]);
+    assert_eq!(Some(1), args.max_results);
+    let args = Args::parse_from(["grepdef", "--max-count", "3", "parseQuery"]);
+    assert_eq!(Some(3), args.max_results);
+}
+
+#[rstest]
+#[case(vec!["parseQuery", "./tests/fixtures/by-language/js-fixture.js"], 0)]
+#[case(vec!["--format", "json", "parseQuery", "./tests/fixtures/by-language/js-fixture.js"], 0)]
+#[case(vec!["notAFunction", "./tests/fixtures/by-language/js-fixture.js"], 1)]
+#[case(vec!["--format", "json", "notAFunction", "./tests/fixtures/by-language/js-fixture.js"], 1)]
+#[case(vec!["--type", "notatype", "parseQuery", "./tests/fixtures"], 2)]
+#[case(vec!["--not-an-option", "parseQuery"], 2)]
+fn cli_exits_with_status_for_results(#[case] args: Vec<&str>, #[case] expected: i32) {
+    let output = Command::new(env!("CARGO_BIN_EXE_grepdef"))
+        .args(args)
+        .output()
+        .expect("Unable to run grepdef");
+    assert_eq!(Some(expected), output.status.code());
+}
+
+#[rstest]
+fn file_type_returns_error_for_unknown_type() {
+    let actual = FileType::from_string("notatype");
+    assert!(matches!(actual, Err(Error::UnknownFileType(file_type)) if file_type == "notatype"));
+}
+
+#[rstest]
+fn searcher_returns_error_for_unknown_type() {
+    let file_path = String::from("./tests/fixtures");
+    let query = String::from("parseQuery");
+    let args = common::make_args(query, Some(file_path), Some(String::from("notatype")));
+    let actual = Searcher::new(args);
+    assert!(matches!(actual, Err(Error::UnknownFileType(file_type)) if file_type == "notatype"));
+}
+
+#[rstest]
+fn searcher_returns_error_when_file_type_cannot_be_guessed() {
+    let file_path = String::from("./tests/fixtures/does-not-exist");
+    let query = String::from("parseQuery");
+    let args = common::make_args(query, Some(file_path), None);
+    let actual = Searcher::new(args);
+    assert!(matches!(
+        actual,
+        Err(Error::CannotGuessFileType(file_paths)) if file_paths == vec!["./tests/fixtures/does-not-exist"]
+    ));
+}
+
+#[rstest]
+fn search_returns_results_and_warning_when_search_path_does_not_exist() {
+    let missing_path = String::from("./tests/fixtures/does-not-exist");
+    let query = String::from("(parseQuery|otherPhpFunction)");
+    let mut args = common::make_args(query, None, Some(String::from("php")));
+    args.file_path = Some(vec![
+        String::from("./tests/fixtures/by-language/php-fixture.php"),
+        String::from("./tests/fixtures/only-php"),
+        missing_path.clone(),
+    ]);
+    let searcher = Searcher::new(args).unwrap();
+    let actual: Vec<String> = searcher
+        .search()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.symbol)
+        .collect();
+    assert_eq!(vec!["parseQuery", "otherPhpFunction"], actual);
+    let warnings: Vec<String> = searcher
+        .warnings()
+        .iter()
+        .map(|warning| warning.to_string())
+        .collect();
+    assert_eq!(
+        vec![format!(
+            "{missing_path}: No such file or directory (os error 2)"
+        )],
+        warnings
+    );
+}
+
+#[rstest]
+fn cli_prints_results_before_missing_search_path() {
+    let output = Command::new(env!("CARGO_BIN_EXE_grepdef"))
+        .args([
+            "-t",
+            "js",
+            "parseQuery",
+            "./tests/fixtures/by-language/js-fixture.js",
+            "./tests/fixtures/does-not-exist",
+        ])
+        .output()
+        .unwrap();
+    assert!(String::from_utf8_lossy(&output.stdout).contains("function parseQuery()"));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("./tests/fixtures/does-not-exist"));
+    assert_eq!(Some(2), output.status.code());
+}
+
+#[cfg(target_os = "linux")]
+#[rstest]
+fn search_returns_warning_for_unreadable_file() {
+    let dir = common::make_temp_dir("unreadable");
+    let file_path = dir.join("broken-link.js");
+    std::os::unix::fs::symlink(dir.join("does-not-exist.js"), &file_path).unwrap();
+    let query = String::from("parseQuery");
+    let args = common::make_args(query, dir.to_str().map(String::from), Some("js".into()));
+    let searcher = Searcher::new(args).unwrap();
+    let actual = searcher.search();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        Vec::<SearchResult>::new(),
+        actual.expect("Search failed for test")
+    );
+    let expected = vec![SearchWarning {
+        file_path,
+        message: String::from("No such file or directory (os error 2)"),
+    }];
+    assert_eq!(expected, searcher.warnings());
+}
+
+#[rstest]
+#[case("./tests/fixtures/by-language/js-fixture.js", "parseQuery")]
+#[case("./tests/fixtures/by-language/invalid-utf-fixture.php", "UTF")]
+fn search_returns_no_warnings_for_readable_files(#[case] file_path: &str, #[case] query: &str) {
+    let args = common::make_args(query.into(), Some(file_path.into()), None);
+    let searcher = Searcher::new(args).unwrap();
+    searcher.search().expect("Search failed for test");
+    assert_eq!(Vec::<SearchWarning>::new(), searcher.warnings());
+}
+
+#[cfg(target_os = "linux")]
+#[rstest]
+#[case(vec![], true)]
+#[case(vec!["--no-messages"], false)]
+fn cli_prints_warnings_to_stderr(#[case] extra_args: Vec<&str>, #[case] expect_warning: bool) {
+    let dir = common::make_temp_dir(&format!("cli-warnings-{}", expect_warning));
+    std::os::unix::fs::symlink(dir.join("does-not-exist.js"), dir.join("broken-link.js")).unwrap();
+    std::fs::write(dir.join("parse.js"), "function parseQuery() {}\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_grepdef"))
+        .args(extra_args)
+        .args(["--type", "js", "parseQuery"])
+        .arg(&dir)
+        .output()
+        .expect("Unable to run grepdef");
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let expected = if expect_warning {
+        format!(
+            "{}: No such file or directory (os error 2)\n",
+            dir.join("broken-link.js").display()
+        )
+    } else {
+        String::new()
+    };
+    assert_eq!(expected, String::from_utf8_lossy(&output.stderr));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("function parseQuery()"));
+    assert_eq!(Some(2), output.status.code());
+}
+
+#[rstest]
+fn search_returns_results_for_text_encodings(
+    #[values(None, Some(SearchMethod::PrescanMemmem), Some(SearchMethod::NoPrescan))]
+    search_method: Option<SearchMethod>,
+    #[values(
+        ("latin1-fixture.php", "parseLatin1Php", 4, 10, 43, "function parseLatin1Php() { // r\u{FFFD}sum\u{FFFD}"),
+        ("latin1-fixture.php", "parseLatin1CommentPhp", 7, 21, 110, "/* caf\u{FFFD} */ function parseLatin1CommentPhp() {}"),
+        ("utf16-fixture.js", "parseUtf16Js", 2, 19, 36, "function parseUtf16Js() {}"),
+        ("utf16be-fixture.js", "parseUtf16BeJs", 1, 19, 20, "function parseUtf16BeJs() {}"),
+        ("utf8-bom-fixture.py", "parse_bom_py", 1, 1, 3, "parse_bom_py = 1"),
+    )]
+    fixture: (&str, &str, usize, usize, usize, &str),
+) {
+    // Positions are counted in the bytes of the file, not of the decoded text.
+    let (file_name, query, line_number, column, byte_offset, text) = fixture;
+    let file_path = format!("./tests/fixtures/by-language/{file_name}");
+    let mut args = common::make_args(query.into(), Some(file_path.clone()), None);
+    args.search_method = search_method;
+    args.column = true;
+    let expected = vec![SearchResult {
+        file_path: file_path.into(),
+        line_number: Some(line_number),
+        column: Some(column),
+        byte_offset: Some(byte_offset),
+        symbol: query.into(),
+        kind: None,
+        text: text.into(),
+    }];
+    assert_eq!(expected, common::do_search(args));
+}
+
+#[cfg(target_os = "linux")]
+#[rstest]
+fn search_returns_results_for_non_utf8_file_names() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let dir = common::make_temp_dir("non-utf8");
+    let file_path = dir.join(OsStr::from_bytes(b"bad-\xff-name.js"));
+    std::fs::write(&file_path, "function parseNonUtf8Path() {}\n").unwrap();
+    let query = String::from("parseNonUtf8Path");
+    let mut args = common::make_args(query, dir.to_str().map(String::from), Some("js".into()));
+    args.column = true;
+    let searcher = Searcher::new(args).unwrap();
+    let actual = searcher.search();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let actual = actual.expect("Search failed for test");
+    assert_eq!(1, actual.len());
+    assert_eq!(file_path, actual[0].file_path);
+    assert!(actual[0]
+        .to_vimgrep()
+        .contains("bad-\u{FFFD}-name.js:1:10:"));
+    assert!(actual[0].to_json().contains("bad-\u{FFFD}-name.js"));
+    assert_eq!(Vec::<SearchWarning>::new(), searcher.warnings());
+}
+
+#[rstest]
+#[case(vec!["-n", "--type", "js", "parseQuery", "-"], "<stdin>:7:function parseQuery() {\n")]
+#[case(vec!["-n", "--stdin-filename", "src/query.js", "parseQuery", "-"], "src/query.js:7:function parseQuery() {\n")]
+#[case(vec!["-n", "--type", "js,php", "--stdin-filename", "query.js", "parseQuery", "-"], "query.js:7:function parseQuery() {\n")]
+#[case(vec!["-n", "--type", "php", "--stdin-filename", "query.js", "parseQuery", "-"], "query.js:7:function parseQuery() {\n")]
+#[case(vec!["-n", "--type", "py,php", "--stdin-filename", "query.js", "parseQuery", "-"], "")]
+#[case(vec!["--vimgrep", "--type", "js", "parseQuery", "-"], "<stdin>:7:10:function parseQuery() {\n")]
+fn cli_searches_stdin(#[case] args: Vec<&str>, #[case] expected: &str) {
+    let fixture = std::fs::read("./tests/fixtures/by-language/js-fixture.js").unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_grepdef"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Unable to run grepdef");
+    child.stdin.take().unwrap().write_all(&fixture).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(expected, String::from_utf8_lossy(&output.stdout));
+}
+
+#[rstest]
+#[case(None)]
+#[case(Some(vec![String::from("all")]))]
+#[case(Some(vec![String::from("js"), String::from("php")]))]
+fn searcher_returns_error_for_stdin_without_file_type(#[case] file_type: Option<Vec<String>>) {
+    let mut args = common::make_args(String::from("parseQuery"), Some(String::from("-")), None);
+    args.file_type = file_type;
+    let actual = Searcher::new(args);
+    assert!(matches!(actual, Err(Error::StdinFileTypeRequired)));
+}
+
+#[rstest]
+fn searcher_returns_error_for_stdin_filename_of_unknown_type() {
+    let mut args = common::make_args(String::from("parseQuery"), Some(String::from("-")), None);
+    args.stdin_filename = Some(String::from("query.unknown"));
+    let actual = Searcher::new(args);
+    assert!(matches!(
+        actual,
+        Err(Error::CannotGuessFileType(file_paths)) if file_paths == vec!["query.unknown"]
+    ));
+}
+
+#[rstest]
+fn search_returns_matching_case_results(
+    #[values(None, Some(SearchMethod::PrescanMemmem), Some(SearchMethod::NoPrescan))]
+    search_method: Option<SearchMethod>,
+    #[values(
+        ("parseQuery", false, false, true),
+        ("parsequery", false, false, false),
+        ("PARSEQUERY", true, false, true),
+        ("parsequery", false, true, true),
+        ("ParseQuery", false, true, false),
+        ("ParseQuery", true, true, true),
+    )]
+    case: (&str, bool, bool, bool),
+) {
+    let (query, ignore_case, smart_case, is_found) = case;
+    let file_path = String::from("./tests/fixtures/by-language/js-fixture.js");
+    let mut args = common::make_args(query.into(), Some(file_path), Some("js".into()));
+    args.search_method = search_method;
+    args.ignore_case = ignore_case;
+    args.smart_case = smart_case;
+    let expected = if is_found {
+        vec![common::get_expected_search_result_for_file_type("js")]
+    } else {
+        vec![]
+    };
+    assert_eq!(expected, common::do_search(args));
+}
+
+#[rstest]
+fn args_parse_case_options() {
+    let args = Args::parse_from(["grepdef", "-i", "-S", "parseQuery"]);
+    assert!(args.ignore_case);
+    assert!(args.smart_case);
+    let args = Args::parse_from(["grepdef", "--ignore-case", "--smart-case", "parseQuery"]);
+    assert!(args.ignore_case);
+    assert!(args.smart_case);
+}
+
+#[rstest]
+fn search_returns_java_class_and_constructors() {
+    let file_path = common::get_default_fixture_for_file_type_string("java").unwrap();
+    let query = String::from("QueryParserJava");
+    let mut args = common::make_args(query, Some(file_path), Some(String::from("java")));
+    args.show_kind = true;
+    let actual: Vec<(usize, DefinitionKind)> = common::do_search(args)
+        .into_iter()
+        .map(|result| (result.line_number.unwrap(), result.kind.unwrap()))
+        .collect();
+    assert_eq!(
+        vec![(5, DefinitionKind::Class), (8, DefinitionKind::Method)],
+        actual
+    );
+}
+
+#[rstest]
+fn search_returns_c_function_with_return_type_on_previous_line() {
+    let file_path = common::get_default_fixture_for_file_type_string("c").unwrap();
+    let query = String::from("format_query_cpp");
+    let mut args = common::make_args(query.clone(), Some(file_path.clone()), None);
+    args.column = true;
+    let expected = vec![SearchResult {
+        file_path: file_path.into(),
+        line_number: Some(40),
+        column: Some(1),
+        byte_offset: Some(624),
+        symbol: query,
+        kind: None,
+        text: String::from("format_query_cpp (const char *query,"),
+    }];
+    assert_eq!(expected, common::do_search(args));
+}
+
+#[rstest]
+#[case(String::from("reset_query_cpp"), DefinitionKind::Method, vec![70])]
+#[case(String::from("reset_query_cpp"), DefinitionKind::Function, vec![])]
+#[case(String::from("format_query_cpp"), DefinitionKind::Function, vec![40])]
+#[case(String::from("format_query_cpp"), DefinitionKind::Method, vec![])]
+fn search_returns_c_split_definitions_of_kind(
+    #[case] query: String,
+    #[case] kind: DefinitionKind,
+    #[case] expected: Vec<usize>,
+) {
+    let file_path = common::get_default_fixture_for_file_type_string("c").unwrap();
+    let mut args = common::make_args(query, Some(file_path), Some(String::from("c")));
+    args.kinds = Some(vec![kind]);
+    let actual: Vec<usize> = common::do_search(args)
+        .into_iter()
+        .map(|result| result.line_number.unwrap())
+        .collect();
+    assert_eq!(expected, actual);
+}
+
+#[rstest]
+fn search_returns_ruby_constant_ignoring_case() {
+    let file_path = common::get_default_fixture_for_file_type_string("rb").unwrap();
+    let mut args = common::make_args(
+        String::from("max_queries_rb"),
+        Some(file_path),
+        Some(String::from("rb")),
+    );
+    args.ignore_case = true;
+    let actual: Vec<usize> = common::do_search(args)
+        .into_iter()
+        .map(|result| result.line_number.unwrap())
+        .collect();
+    assert_eq!(vec![3], actual);
+}
+
+#[rstest]
+fn search_returns_csharp_definitions_for_search_methods(
+    #[values(None, Some(SearchMethod::PrescanMemmem), Some(SearchMethod::NoPrescan))]
+    search_method: Option<SearchMethod>,
+    #[values(
+        ("cs-fixture.cs", "QueryParserCs", vec![26, 28]),
+        ("cs-fixture.cs", "RunQueryCs", vec![9, 49]),
+        ("cs-namespace-fixture.cs", "QueryBlockCs", vec![1]),
+        ("cs-namespace-fixture.cs", "QueryBlockClassCs", vec![3]),
+    )]
+    fixture: (&str, &str, Vec<usize>),
+) {
+    let (file_name, query, expected) = fixture;
+    let file_path = format!("./tests/fixtures/by-language/{file_name}");
+    let mut args = common::make_args(query.into(), Some(file_path), None);
+    args.search_method = search_method;
+    let actual: Vec<usize> = common::do_search(args)
+        .into_iter()
+        .map(|result| result.line_number.unwrap())
+        .collect();
+    assert_eq!(expected, actual);
+}
+
+#[rstest]
+fn searcher_returns_error_for_invalid_query() {
+    let file_path = common::get_default_fixture_for_file_type_string("js").unwrap();
+    let args = common::make_args(
+        String::from("parseQuery("),
+        Some(file_path),
+        Some(String::from("js")),
+    );
+    let actual = Searcher::new(args);
+    assert!(matches!(actual, Err(Error::InvalidQuery(query)) if query == "parseQuery("));
+}
+
+#[rstest]
+fn cli_exits_with_error_for_invalid_query() {
+    let output = Command::new(env!("CARGO_BIN_EXE_grepdef"))
+        .args([
+            "--format",
+            "json",
+            "-t",
+            "js",
+            "parseQuery(",
+            "./tests/fixtures",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!("", String::from_utf8_lossy(&output.stdout));
+    assert_eq!(
+        "Invalid query 'parseQuery('\n",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(Some(2), output.status.code());
+}